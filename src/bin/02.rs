@@ -1,6 +1,6 @@
 advent_of_code::solution!(2);
 
-fn solve(row: &Vec<i32>, recurse: bool) -> bool {
+fn solve(row: &[i32], recurse: bool) -> bool {
     let mut safe = true;
     let inc = if row[0] > row[1] {
         1
@@ -11,7 +11,7 @@ fn solve(row: &Vec<i32>, recurse: bool) -> bool {
     };
     for i in 1..(row.len()) {
         let diff = (row[i - 1] - row[i]) * inc;
-        safe = diff >= 1 && diff <= 3;
+        safe = (1..=3).contains(&diff);
 
         if !recurse && !safe {
             break;
@@ -1,37 +1,41 @@
-use advent_of_code::parse::Parseable;
-use std::iter::from_fn;
+use advent_of_code::parse::{
+    cursor::{parse_all, Cursor, ParseError},
+    sections2, SectionError,
+};
 
 advent_of_code::solution!(5);
 
-fn parse(input: &str) -> (Vec<Vec<u32>>, Vec<Vec<u32>>) {
-    let mut rules = Vec::new();
+/// For each page, the pages that must come after it.
+type Rules = Vec<Vec<u32>>;
 
-    // use an iterator so we can maintain position across rules and jobs
-    let mut line_iter = input.lines().into_iter();
-    while let Some(line) = line_iter.next() {
-        if line.is_empty() {
-            break;
-        }
+fn parse_rules(section: &str) -> Result<Rules, ParseError> {
+    let pairs = parse_all(section, |cursor| {
+        cursor.lines(|c| {
+            let x: usize = c.number()?;
+            c.expect_literal("|")?;
+            Ok((x, c.number()?))
+        })
+    })?;
 
-        let mut bytes = line.bytes();
-        let x: usize = bytes.next_number().unwrap();
-        let y = bytes.next_number().unwrap();
+    let mut rules = Vec::new();
+    for (x, y) in pairs {
         if x >= rules.len() {
             rules.resize(x + 1, Vec::new());
         }
         rules[x].push(y);
     }
+    Ok(rules)
+}
 
-    let mut jobs = Vec::new();
-    while let Some(line) = line_iter.next() {
-        let mut bytes = line.bytes();
-        jobs.push(from_fn(|| bytes.next_number()).collect());
-    }
+fn parse_jobs(section: &str) -> Result<Vec<Vec<u32>>, ParseError> {
+    parse_all(section, |cursor| cursor.lines(|c| c.sep_by(",", Cursor::number)))
+}
 
-    (rules, jobs)
+fn parse(input: &str) -> Result<(Rules, Vec<Vec<u32>>), SectionError> {
+    sections2(input, parse_rules, parse_jobs)
 }
 
-fn check(job: &[u32], rules: &Vec<Vec<u32>>) -> Option<u32> {
+fn check(job: &[u32], rules: &[Vec<u32>]) -> Option<u32> {
     for i in 0..job.len() - 1 {
         if !rules[job[i] as usize].contains(&job[i + 1]) {
             return None;
@@ -40,7 +44,7 @@ fn check(job: &[u32], rules: &Vec<Vec<u32>>) -> Option<u32> {
     Some(job[(job.len() - 1) / 2])
 }
 
-fn correct(job: &mut Vec<u32>, rules: &Vec<Vec<u32>>) -> u32 {
+fn correct(job: &mut [u32], rules: &[Vec<u32>]) -> u32 {
     job.sort_by(|a, b| {
         if rules[*a as usize].contains(b) {
             std::cmp::Ordering::Less
@@ -52,7 +56,7 @@ fn correct(job: &mut Vec<u32>, rules: &Vec<Vec<u32>>) -> u32 {
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let (rules, jobs) = parse(input);
+    let (rules, jobs) = parse(input).ok()?;
 
     jobs.iter()
         .filter_map(|job| check(job, &rules))
@@ -61,11 +65,11 @@ pub fn part_one(input: &str) -> Option<u32> {
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let (rules, mut jobs) = parse(input);
+    let (rules, mut jobs) = parse(input).ok()?;
 
     jobs.iter_mut()
         .filter(|job| check(job, &rules).is_none())
-        .map(|mut job| correct(&mut job, &rules))
+        .map(|job| correct(job, &rules))
         .sum::<u32>()
         .into()
 }
@@ -1,7 +1,7 @@
-use std::collections::HashSet;
-
+use advent_of_code::bitgrid::BitGrid;
 use advent_of_code::point::Point;
-use advent_of_code::pointmap::{Direction, PointMap};
+use advent_of_code::pointmap::{parse::GridParseError, Direction, PointMap};
+use advent_of_code::search::Visited;
 
 advent_of_code::solution!(6);
 
@@ -11,71 +11,53 @@ enum Square {
     Obstacle,
 }
 
-fn parse(input: &str) -> (PointMap<Square>, Point) {
-    let mut vec = Vec::new();
-
-    let mut height = 0;
-    let mut start = Point::new(0, 0);
-
-    for l in input.lines() {
-        for (x, b) in l.bytes().enumerate() {
-            match b {
-                b'.' => vec.push(Square::Empty),
-                b'#' => vec.push(Square::Obstacle),
-                b'^' => {
-                    start.x = x;
-                    start.y = height;
-                    vec.push(Square::Empty);
-                }
-                _ => panic!("Invalid character: {}", b as char),
-            }
-        }
-        height += 1;
-    }
+fn parse(input: &str) -> Result<(PointMap<Square>, Point), GridParseError> {
+    let (map, [start]) = PointMap::parse_with_markers(input, [b'^'], |b| match b {
+        b'.' | b'^' => Some(Square::Empty),
+        b'#' => Some(Square::Obstacle),
+        _ => None,
+    })?;
 
-    (PointMap::from_vec(vec, height), start)
+    Ok((map, start))
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let (map, start) = parse(input);
+    let (map, start) = parse(input).ok()?;
 
-    let mut visited = PointMap::from_vec(vec![false; map.width * map.height], map.height);
+    let mut visited = BitGrid::for_map(&map);
     let mut pos = start;
-    let mut count = 1;
     let mut dir = Direction::North;
-    visited[pos] = true;
+    visited.insert(pos);
 
     while let Some(next) = map.step(pos, dir) {
         if map[next] == Square::Obstacle {
             dir = dir.rotate_clockwise();
             continue;
-        } else if !visited[next] {
-            visited[next] = true;
-            count += 1;
         }
 
+        visited.insert(next);
         pos = next;
     }
 
-    Some(count)
+    Some(visited.count() as u32)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let (map, start) = parse(input);
+    let (map, start) = parse(input).ok()?;
 
-    let mut visited = PointMap::from_vec(vec![[false; 4]; map.width * map.height], map.height);
+    let empty = BitGrid::for_map(&map);
+    let mut visited = [(); 4].map(|_| empty.clone());
     let mut pos = start;
     let mut dir = Direction::North;
-    let mut loops = HashSet::new();
-    visited[pos][0] = true;
+    let mut loops = empty.clone();
+    visited.visit((pos, dir));
 
     while let Some(next) = map.step(pos, dir) {
         if map[next] == Square::Obstacle {
             dir = dir.rotate_clockwise();
             continue;
-        } else if visited[next].iter().all(|seen| !seen) {
-            let mut visited_dup =
-                PointMap::from_vec(vec![[false; 4]; map.width * map.height], map.height);
+        } else if Direction::ALL.iter().all(|d| !visited.is_visited(&(next, *d))) {
+            let mut visited_dup = [(); 4].map(|_| empty.clone());
             let mut dir_dup = dir.rotate_clockwise();
             let mut pos_dup = pos;
 
@@ -83,23 +65,23 @@ pub fn part_two(input: &str) -> Option<u32> {
                 if map[next_dup] == Square::Obstacle || next_dup == next {
                     dir_dup = dir_dup.rotate_clockwise();
                     continue;
-                } else if visited_dup[next_dup][dir_dup as usize]
-                    || visited[next_dup][dir_dup as usize]
+                } else if visited_dup.is_visited(&(next_dup, dir_dup))
+                    || visited.is_visited(&(next_dup, dir_dup))
                 {
                     loops.insert(next);
                     break;
                 }
 
-                visited_dup[next_dup][dir_dup as usize] = true;
+                visited_dup.visit((next_dup, dir_dup));
                 pos_dup = next_dup;
             }
         }
 
-        visited[next][dir as usize] = true;
+        visited.visit((next, dir));
         pos = next;
     }
 
-    Some(loops.len() as u32)
+    Some(loops.count() as u32)
 }
 
 #[cfg(test)]
@@ -36,7 +36,7 @@ fn solvable(eq: &Equation, rem: u64, idx: usize) -> Option<u64> {
     let idx = idx - 1;
     let next = eq.inputs[idx];
 
-    if (rem % next == 0 && solvable(eq, rem / next, idx).is_some())
+    if (rem.is_multiple_of(next) && solvable(eq, rem / next, idx).is_some())
         || (rem >= next && solvable(eq, rem - next, idx).is_some()) {
         return Some(eq.result);
     }
@@ -75,8 +75,8 @@ fn process_two_operators(target: u64, components: &[u64], idx: usize, acc: u64)
         return acc == target;
     }
 
-    return process_two_operators(target, components, idx + 1, acc + components[idx]) ||
-        process_two_operators(target, components, idx + 1, acc * components[idx]);
+    process_two_operators(target, components, idx + 1, acc + components[idx]) ||
+        process_two_operators(target, components, idx + 1, acc * components[idx])
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
@@ -85,7 +85,7 @@ pub fn part_one(input: &str) -> Option<u64> {
     equations
         .iter()
         .filter_map(|eq| {
-            solvable(&eq, eq.result, eq.inputs.len())
+            solvable(eq, eq.result, eq.inputs.len())
         })
         .sum::<u64>()
         .into()
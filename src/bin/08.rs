@@ -3,7 +3,7 @@ advent_of_code::solution!(8);
 use std::collections::{HashMap, HashSet};
 use itertools::Itertools;
 
-use advent_of_code::{point::{ipoint::IPoint, Point}, pointmap::PointMap};
+use advent_of_code::{point::{ipoint::IPoint, line::Walk}, rect::Rect, pointmap::{parse::GridParseError, render::{debug, ToGridChar}, PointMap}};
 
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -13,25 +13,24 @@ enum Tile {
     Antenna(char),
 }
 
-fn parse_map(input: &str) -> PointMap<Tile> {
-    let mut vec = Vec::new();
-    let mut height = 0;
-
-    for line in input.lines() {
-        for c in line.bytes() {
-            match c {
-                b'.' => vec.push(Tile::Empty),
-                _ => vec.push(Tile::Antenna(c as char)),
-            }
+impl ToGridChar for Tile {
+    fn to_grid_char(&self) -> char {
+        match self {
+            Tile::Empty => '.',
+            Tile::Antinode => '#',
+            Tile::Antenna(c) => *c,
         }
-        height += 1;
     }
-
-    PointMap::from_vec(vec, height)
 }
 
+fn parse_map(input: &str) -> Result<PointMap<Tile>, GridParseError> {
+    PointMap::parse_with(input, |c| match c {
+        b'.' => Some(Tile::Empty),
+        _ => Some(Tile::Antenna(c as char)),
+    })
+}
 
-fn parse(input: &str) -> (HashMap<u8, Vec<IPoint>>, usize, usize) {
+fn parse(input: &str) -> (HashMap<u8, Vec<IPoint>>, Rect<isize>) {
     let mut map: HashMap<u8, Vec<IPoint>> = HashMap::new();
 
     let mut y = 0;
@@ -44,45 +43,17 @@ fn parse(input: &str) -> (HashMap<u8, Vec<IPoint>>, usize, usize) {
             match c {
                 b'.' => continue,
                 _ =>
-                    map.entry(c).or_default().push(IPoint::new(x as isize, y as isize))
+                    map.entry(c).or_default().push(IPoint::new(x as isize, y))
             }
         }
         y+=1;
     }
 
-    (map, width, y)
-}
-
-trait BoundedPoint {
-    fn in_bounds(&self, width: usize, height: usize) -> bool;
-    fn bounded_sub(&self, other: IPoint, width: usize, height: usize) -> Option<IPoint>;
-    fn bounded_add(&self, other: IPoint, width: usize, height: usize) -> Option<IPoint>;
-}
-
-impl BoundedPoint for IPoint {
-    fn in_bounds(&self, width: usize, height: usize) -> bool {
-        self.x < width as isize && self.y < height as isize && self.x >= 0 && self.y >= 0
-    }
-
-    fn bounded_add(&self, other: IPoint, width: usize, height: usize) -> Option<IPoint> {
-        let p = *self + other;
-        if p.in_bounds(width, height) {
-            return Some(p);
-        }
-        None
-    }
-
-    fn bounded_sub(&self, other: IPoint, width: usize, height: usize) -> Option<IPoint> {
-        let p = *self - other;
-        if p.in_bounds(width, height) {
-            return Some(p);
-        }
-        None
-    }
+    (map, Rect::from_size(width, y))
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let (map, width, height) = parse(input);
+    let (map, bounds) = parse(input);
 
     let mut antinodes = HashSet::new();
     for pos in map.values() {
@@ -91,14 +62,10 @@ pub fn part_one(input: &str) -> Option<usize> {
             let a2 = *pair[1];
 
             let diff = a2 - a1;
-            let anti1 = a2.bounded_add(diff, width, height);
-            let anti2 = a1.bounded_sub(diff, width, height);
-
-            if let Some(p) = anti1 {
-                antinodes.insert(p);
-            }
-            if let Some(p) = anti2 {
-                antinodes.insert(p);
+            for p in [a2 + diff, a1 - diff] {
+                if bounds.contains(p) {
+                    antinodes.insert(p);
+                }
             }
         }
     }
@@ -107,50 +74,28 @@ pub fn part_one(input: &str) -> Option<usize> {
 }
 
 pub fn part_two(input: &str) -> Option<usize> {
-    let (map, width, height) = parse(input);
+    let (map, bounds) = parse(input);
 
     let mut antinodes = HashSet::new();
     for pos in map.values() {
         for pair in pos.iter().combinations(2) {
-            let mut a1 = *pair[0];
-            let mut a2 = *pair[1];
+            let a1 = *pair[0];
+            let a2 = *pair[1];
 
+            // Antinodes repeat at the antennas' own spacing outwards from each
+            // end. A pair's own antennas only count when another pair reaches them.
             let diff = a2 - a1;
-            loop {
-                let anti1 = a2.bounded_add(diff, width, height);
-                let anti2 = a1.bounded_sub(diff, width, height);
-                let mut added = false;
-
-                if let Some(p) = anti1 {
-                    a2 = p;
-                    antinodes.insert(p);
-                    added = true;
-                }
-                if let Some(p) = anti2 {
-                    a1 = p;
-                    antinodes.insert(p);
-                    added = true;
-                }
-                if !added {
-                    break;
-                }
-            }
+            antinodes.extend(Walk::new(a2 + diff, diff, bounds));
+            antinodes.extend(Walk::new(a1 - diff, -diff, bounds));
         }
     }
 
-    let mut m = parse_map(input);
+    let mut m = parse_map(input).ok()?;
     for ele in antinodes.iter() {
-        m.set(Point::from_ipoint(*ele), Tile::Antinode);
+        m[*ele] = Tile::Antinode;
     }
 
-    for y in 0..m.height {
-        let row = m.row(y);
-        println!("{}", row.iter().map(|t| match t {
-            Tile::Empty => '.',
-            Tile::Antinode => '#',
-            Tile::Antenna(c) => *c
-        }).collect::<String>());
-    }
+    debug(&m);
 
    Some(antinodes.len())
 }
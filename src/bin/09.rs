@@ -1,3 +1,5 @@
+use advent_of_code::parse::formats::NumberFormats;
+
 advent_of_code::solution!(9);
 
 enum Entry {
@@ -7,9 +9,11 @@ enum Entry {
 
 fn parse_input(input: &str) -> Vec<Entry> {
     input
-        .char_indices()
-        .map(|(i, c)| {
-            let size = c.to_digit(10).unwrap() as usize;
+        .bytes()
+        .digits()
+        .enumerate()
+        .map(|(i, size)| {
+            let size = size as usize;
             if i % 2 == 0 {
                 let id = i / 2;
                 Entry::File { id, size }
@@ -135,7 +139,7 @@ mod tests {
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let _result = part_two(&advent_of_code::template::read_file("examples", DAY));
         //assert_eq!(result, Some(2858));
     }
 }
@@ -1,63 +1,45 @@
-use std::collections::{HashSet, HashMap};
-
-use advent_of_code::{pointmap::PointMap, point::Point};
+use advent_of_code::{parse::ToDigit, pointmap::PointMap, point::Point};
 
 advent_of_code::solution!(10);
 
-fn parse(input: &str) -> PointMap<u8>{
-    let mut vec = Vec::new();
-    let mut height = 0;
-    for line in input.lines() {
-        for b in line.bytes() {
-            vec.push(b);
-        }
-        height += 1;
-    }
-    PointMap::from_vec(vec, height)
+fn is_uphill(from: &u8, to: &u8) -> bool {
+    *to == from + 1
 }
 
-fn count_trail_heads(map: &PointMap<u8>, start: Point, unique: bool) -> u64 {
-    let mut visited = HashSet::<Point>::new();
-    let mut queue = Vec::new();
-    let mut trail_ends = HashMap::<Point, u64>::new();
+fn count_trail_ends(map: &PointMap<u8>, start: Point) -> u64 {
+    map.reachable(start, is_uphill)
+        .iter()
+        .filter(|p| map[*p] == 9)
+        .count() as u64
+}
 
-    queue.push(start);
-    while let Some(p) = queue.pop() {
-        if unique && visited.contains(&p) {
-            continue;
-        }
-        visited.insert(p);
+fn count_trails(map: &PointMap<u8>, start: Point) -> u64 {
+    let mut queue = vec![start];
+    let mut trails = 0;
 
-        if *map.at(p) == b'9' {
-            *trail_ends.entry(p).or_default() += 1;
+    while let Some(p) = queue.pop() {
+        if *map.at(p) == 9 {
+            trails += 1;
         }
-        let target_height = map.at(p) + 1;
-        for n in map.neighbors(p) {
-            if n.is_none() {
-                continue;
+        for n in map.neighbors(p).into_iter().flatten() {
+            if is_uphill(map.at(p), map.at(n)) {
+                queue.push(n);
             }
-
-            let n = n.unwrap();
-            if *map.at(n) != target_height  || (visited.contains(&n) && unique) {
-                continue;
-            }
-
-            queue.push(n);
         }
     }
 
-    trail_ends.values().into_iter().sum()
+    trails
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let map = parse(input);
+    let map = PointMap::parse_with(input, |b| b.to_digit()).ok()?;
     let mut result = 0;
     for y in 0..map.height {
         for x in 0..map.width {
             let p = Point::new(x, y);
             let c = map.at(p);
-            if *c == b'0' {
-                result += count_trail_heads(&map, p, true)
+            if *c == 0 {
+                result += count_trail_ends(&map, p)
             }
         }
     }
@@ -66,14 +48,14 @@ pub fn part_one(input: &str) -> Option<u64> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let map = parse(input);
+    let map = PointMap::parse_with(input, |b| b.to_digit()).ok()?;
     let mut result = 0;
     for y in 0..map.height {
         for x in 0..map.width {
             let p = Point::new(x, y);
             let c = map.at(p);
-            if *c == b'0' {
-                result += count_trail_heads(&map, p, false)
+            if *c == 0 {
+                result += count_trails(&map, p)
             }
         }
     }
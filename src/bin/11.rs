@@ -19,7 +19,7 @@ fn blink(stone: u64, times: u32, cache: &mut HashMap<u64, u64>) -> u64 {
         return *cached;
     }
 
-    let len = if stone < 10 { 1 } else { stone.ilog10() as u32 + 1};
+    let len = if stone < 10 { 1 } else { stone.ilog10() + 1};
     let sum = if times == 1 {
         return if len % 2 == 0 {
             2
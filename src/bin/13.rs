@@ -1,67 +1,29 @@
-use advent_of_code::parse::Parseable;
-
 advent_of_code::solution!(13);
 
-// Input is formatted as:
-//
-// Button A: X+94, Y+34
-// Button B: X+22, Y+67
-// Prize: X=8400, Y=5400
-//
-// This defines a single ClawMachine.  There are multiple machines, delimted by a blank New Line
-
-#[derive(Debug)]
-struct ClawMachine {
-    button_a: (i64, i64),
-    button_b: (i64, i64),
-    prize: (i64, i64),
-}
-
-fn parse(input: &str) -> Vec<ClawMachine> {
-    let mut machines = Vec::new();
-    let mut lines = input.lines();
-    while let Some(line) = lines.next() {
-        if line.is_empty() {
-            continue;
-        }
-        let button_a = line;
-        let button_b = lines.next().unwrap();
-        let prize = lines.next().unwrap();
-        let mut button_a = button_a.bytes();
-        let button_a = (
-            button_a.next_number().unwrap(),
-            button_a.next_number().unwrap(),
-        );
-        let mut button_b = button_b.bytes();
-        let button_b = (
-            button_b.next_number().unwrap(),
-            button_b.next_number().unwrap(),
-        );
-        let mut prize = prize.bytes();
-        let prize = (prize.next_number().unwrap(), prize.next_number().unwrap());
-        machines.push(ClawMachine {
-            button_a,
-            button_b,
-            prize,
-        });
+// There are multiple machines, delimited by a blank line.
+advent_of_code::parse_record! {
+    #[derive(Debug)]
+    struct ClawMachine {
+        "Button A: X+", ax: i64, ", Y+", ay: i64, "\n",
+        "Button B: X+", bx: i64, ", Y+", by: i64, "\n",
+        "Prize: X=", px: i64, ", Y=", py: i64,
     }
-    machines
 }
 
 // solve a pair of linear equations such that:
-// given a = button_a.0, b = button_a.1, c = button_b.0, d = button_b.1, x = prize.0, y = prize.1
+// given a = ax, b = ay, c = bx, d = by, x = px, y = py
 // where A and B are integers from 0..100
 // find A, B such that:
 // a * A + b * A = x
 // c * B + d * B = y
 fn solve(cm: &ClawMachine, offset: i64) -> Option<u64> {
-    let x = cm.prize.0 + offset;
-    let y = cm.prize.1 + offset;
+    let x = cm.px + offset;
+    let y = cm.py + offset;
 
-    let a = cm.button_a.0;
-    let c = cm.button_a.1;
-    let b = cm.button_b.0;
-    let d = cm.button_b.1;
+    let a = cm.ax;
+    let c = cm.ay;
+    let b = cm.bx;
+    let d = cm.by;
 
     let left = a * y - c * x;
     let right = a * d - b * c;
@@ -81,7 +43,7 @@ fn solve(cm: &ClawMachine, offset: i64) -> Option<u64> {
 }
 
 pub fn part_one(input: &str) -> Option<u64> {
-    let machines = parse(input);
+    let machines = ClawMachine::parse_records(input).ok()?;
 
     machines
         .iter()
@@ -91,7 +53,7 @@ pub fn part_one(input: &str) -> Option<u64> {
 }
 
 pub fn part_two(input: &str) -> Option<u64> {
-    let machines = parse(input);
+    let machines = ClawMachine::parse_records(input).ok()?;
 
     machines
         .iter()
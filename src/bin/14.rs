@@ -1,6 +1,6 @@
-use std::{ffi::FromVecWithNulError, iter::from_fn};
+use std::iter::from_fn;
 
-use advent_of_code::{point::{ipoint::IPoint, traits::{ModuloPositive, Absolute}}, parse::Parseable, math::solve_linear_diophantine};
+use advent_of_code::{point::{ipoint::IPoint, traits::Absolute}, parse::Parseable, math::solve_linear_diophantine, rect::Rect};
 
 advent_of_code::solution!(14);
 
@@ -11,9 +11,8 @@ struct Robot {
 }
 
 impl Robot {
-    fn siumulate(&mut self, time: isize, dims: IPoint) {
-        let pos = (self.pos + self.velocity * time).modulo_positive(dims);
-        self.pos = pos;
+    fn siumulate(&mut self, time: isize, area: &Rect<isize>) {
+        self.pos = area.wrap(self.pos + self.velocity * time);
     }
 }
 
@@ -35,39 +34,29 @@ pub fn part_one(input: &str) -> Option<usize> {
 fn solve_one(input: &str, width: isize, height: isize) -> usize {
     let mut robots = parse(input);
 
-    let quad_width = width / 2;
-    let quad_height = height / 2;
-
-    let mut quads = vec![0; 4];
-    let dims = IPoint::new(width, height);
+    let area = Rect::from_size(width, height);
 
     for robot in robots.iter_mut() {
-        robot.siumulate(100, dims);
-
-        if robot.pos.x == quad_width || robot.pos.y == quad_height {
-            continue;
-        }
-
-        let quad = if robot.pos.x > quad_width { 0b10 } else { 0 }
-            | if robot.pos.y > quad_height { 1 } else { 0 };
-
-        quads[quad] += 1;
+        robot.siumulate(100, &area);
     }
 
-    quads.iter().product()
+    area.quadrants()
+        .iter()
+        .map(|quad| robots.iter().filter(|r| quad.contains(r.pos)).count())
+        .product()
 }
 
 fn solve_two(input: &str, width: isize, height: isize) -> usize {
     let mut robots = parse(input);
 
-    let dims = IPoint::new(width, height);
+    let area = Rect::from_size(width, height);
     let mut x_start = None;
     let mut y_start = None;
     let mut i = 1;
 
     while x_start.is_none() || y_start.is_none() {
         for robot in robots.iter_mut() {
-            robot.siumulate(1, dims);
+            robot.siumulate(1, &area);
         }
 
         let var = variance(&robots);
@@ -90,7 +79,7 @@ fn solve_two(input: &str, width: isize, height: isize) -> usize {
     0
 }
 
-fn variance(robots: &Vec<Robot>) -> IPoint {
+fn variance(robots: &[Robot]) -> IPoint {
     let average = robots.iter().map(|r| r.pos).sum::<IPoint>() / robots.len() as isize;
     robots.iter().map(|r| (average - r.pos).absolute()).sum()
 }
@@ -107,10 +96,19 @@ pub fn part_two(input: &str) -> Option<usize> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse() {
+        let robots = parse("p=0,4 v=3,-3\np=-6,3 v=-1,-30\n");
+        assert_eq!(robots[0].pos, IPoint::new(0, 4));
+        assert_eq!(robots[0].velocity, IPoint::new(3, -3));
+        assert_eq!(robots[1].pos, IPoint::new(-6, 3));
+        assert_eq!(robots[1].velocity, IPoint::new(-1, -30));
+    }
+
     #[test]
     fn test_part_one() {
-        //let result = solve_one(&advent_of_code::template::read_file("examples", DAY), 11, 7);
-        //assert_eq!(result, 12);
+        let result = solve_one(&advent_of_code::template::read_file("examples", DAY), 11, 7);
+        assert_eq!(result, 12);
     }
 
     #[test]
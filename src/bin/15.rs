@@ -1,4 +1,4 @@
-use advent_of_code::{pointmap::{Direction, PointMap}, point::Point};
+use advent_of_code::{pointmap::{parse::GridParseError, Direction, PointMap}, point::Point, parse::{cursor::{parse_all, ParseError}, sections2, SectionError}};
 
 advent_of_code::solution!(15);
 
@@ -9,52 +9,48 @@ enum Cell {
     Box,
 }
 
-fn parse(input: &str) -> (PointMap<Cell>, Point, Vec<Direction>) {
-    // The input is delimeted by a newline, where the first section is a map.
-    // In the map, the following characters are used:
-    //   . = Empty
-    //   # = Wall
-    //   O = Box
-    //   @ = Start
-    let mut vec = Vec::new();
-    let mut start = Point::new(0, 0);
-    let mut height = 0;
-    let mut lines = input.lines();
+// The input is delimeted by a newline, where the first section is a map.
+// In the map, the following characters are used:
+//   . = Empty
+//   # = Wall
+//   O = Box
+//   @ = Start
+fn parse_grid(section: &str) -> Result<(PointMap<Cell>, Point), GridParseError> {
+    let (map, [start]) = PointMap::parse_with_markers(section, [b'@'], |c| match c {
+        b'.' | b'@' => Some(Cell::Empty),
+        b'#' => Some(Cell::Wall),
+        b'O' => Some(Cell::Box),
+        _ => None,
+    })?;
+    Ok((map, start))
+}
 
-    // first parse the map into a PointMap from a Vec of Cells
-    while let Some(line) = lines.next() {
-        if line.is_empty() { break; }
-        for (x, c) in line.bytes().enumerate() {
-            match c {
-                b'.' => vec.push(Cell::Empty),
-                b'#' => vec.push(Cell::Wall),
-                b'O' => vec.push(Cell::Box),
-                b'@' => {
-                    vec.push(Cell::Empty);
-                    start = Point::new(x, height);
-                },
-                _ => panic!("Invalid character in input"),
-            }
-        }
-        height+=1;
-    }
-    let map = PointMap::from_vec(vec, height);
-    let mut dirs = Vec::new();
-    // Next parse the directions, where
-    // ^ is north, > is east, v is south and < is west.
-    while let Some(line) = lines.next() {
-        for c in line.bytes() {
-            match c {
-                b'^' => dirs.push(Direction::North),
-                b'>' => dirs.push(Direction::East),
-                b'v' => dirs.push(Direction::South),
-                b'<' => dirs.push(Direction::West),
-                _ => panic!("Invalid character in input"),
+// Next parse the directions, where
+// ^ is north, > is east, v is south and < is west.
+fn parse_moves(section: &str) -> Result<Vec<Direction>, ParseError> {
+    const MOVES: [(&str, Direction); 4] = [
+        ("^", Direction::North),
+        (">", Direction::East),
+        ("v", Direction::South),
+        ("<", Direction::West),
+    ];
+
+    let lines = parse_all(section, |cursor| {
+        cursor.lines(|c| {
+            let mut dirs = Vec::new();
+            let mut next = || MOVES.iter().find_map(|(s, dir)| c.eat_literal(s).then_some(*dir));
+            while let Some(dir) = next() {
+                dirs.push(dir);
             }
-        }
-    }
+            Ok(dirs)
+        })
+    })?;
+    Ok(lines.concat())
+}
 
-    (map, start, dirs)
+fn parse(input: &str) -> Result<(PointMap<Cell>, Point, Vec<Direction>), SectionError> {
+    let ((map, start), dirs) = sections2(input, parse_grid, parse_moves)?;
+    Ok((map, start, dirs))
 }
 
 fn move_boxes(map: &mut PointMap<Cell>, pos: Point, dir: Direction) -> Point {
@@ -86,7 +82,7 @@ fn move_boxes(map: &mut PointMap<Cell>, pos: Point, dir: Direction) -> Point {
 }
 
 pub fn part_one(input: &str) -> Option<usize> {
-    let (mut map, start, dirs) = parse(input);
+    let (mut map, start, dirs) = parse(input).ok()?;
     let mut pos = start;
     for dir in dirs {
         pos = move_boxes(&mut map, pos, dir);
@@ -103,7 +99,7 @@ pub fn part_one(input: &str) -> Option<usize> {
         .sum::<usize>().into()
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
+pub fn part_two(_input: &str) -> Option<u32> {
     None
 }
 
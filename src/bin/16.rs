@@ -1,11 +1,61 @@
+use advent_of_code::{
+    bitgrid::BitGrid,
+    point::Point,
+    pointmap::{Direction, PointMap},
+    search::{dijkstra_all, ShortestPaths},
+};
+
 advent_of_code::solution!(16);
 
-pub fn part_one(input: &str) -> Option<u32> {
-    None
+const STEP_COST: u64 = 1;
+const TURN_COST: u64 = 1000;
+
+fn parse(input: &str) -> Option<(PointMap<bool>, Point, Point)> {
+    // '#' is a wall, while the start (S) and end (E) tiles are open floor
+    let (map, [start, end]) = PointMap::parse_with_markers(input, [b'S', b'E'], |b| match b {
+        b'#' => Some(false),
+        b'.' | b'S' | b'E' => Some(true),
+        _ => None,
+    })
+    .ok()?;
+
+    Some((map, start, end))
+}
+
+fn search(map: &PointMap<bool>, start: Point, end: Point) -> ShortestPaths<(Point, Direction)> {
+    // The reindeer starts facing east, and can either step forwards or turn on the spot
+    dijkstra_all(
+        [(start, Direction::East)],
+        |&(pos, dir)| {
+            let forward = map
+                .step(pos, dir)
+                .filter(|next| map[*next])
+                .map(|next| ((next, dir), STEP_COST));
+
+            [
+                forward,
+                Some(((pos, dir.rotate_clockwise()), TURN_COST)),
+                Some(((pos, dir.rotate_counterclockwise()), TURN_COST)),
+            ]
+            .into_iter()
+            .flatten()
+        },
+        &mut std::array::from_fn(|_| BitGrid::for_map(map)),
+        |&(pos, _)| pos == end,
+    )
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    None
+pub fn part_one(input: &str) -> Option<u64> {
+    let (map, start, end) = parse(input)?;
+
+    search(&map, start, end).goal_distance()
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    let (map, start, end) = parse(input)?;
+
+    // Every tile on any of the best paths is a good seat
+    Some(search(&map, start, end).points_on_paths().len())
 }
 
 #[cfg(test)]
@@ -15,12 +65,12 @@ mod tests {
     #[test]
     fn test_part_one() {
         let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(11048));
     }
 
     #[test]
     fn test_part_two() {
         let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
+        assert_eq!(result, Some(64));
     }
 }
@@ -0,0 +1,168 @@
+use crate::{point::Point, pointmap::PointMap};
+
+const BITS: usize = u64::BITS as usize;
+
+/// A set of points on a `width` by `height` grid, stored as one bit per cell
+/// in the same row order as [`PointMap`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitGrid {
+    words: Vec<u64>,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl BitGrid {
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            words: vec![0; (width * height).div_ceil(BITS)],
+            width,
+            height,
+        }
+    }
+
+    /// An empty set the same size as `map`.
+    pub fn for_map<T>(map: &PointMap<T>) -> Self {
+        Self::new(map.width, map.height)
+    }
+
+    fn index(&self, point: Point) -> (usize, u64) {
+        debug_assert!(point.x < self.width && point.y < self.height);
+        let i = self.width * point.y + point.x;
+        (i / BITS, 1 << (i % BITS))
+    }
+
+    /// Adds `point`, returning whether it was not already present.
+    pub fn insert(&mut self, point: Point) -> bool {
+        let (word, mask) = self.index(point);
+        let added = self.words[word] & mask == 0;
+        self.words[word] |= mask;
+        added
+    }
+
+    /// Removes `point`, returning whether it was present.
+    pub fn remove(&mut self, point: Point) -> bool {
+        let (word, mask) = self.index(point);
+        let present = self.words[word] & mask != 0;
+        self.words[word] &= !mask;
+        present
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        let (word, mask) = self.index(point);
+        self.words[word] & mask != 0
+    }
+
+    pub fn clear(&mut self) {
+        self.words.fill(0);
+    }
+
+    pub fn count(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|w| *w == 0)
+    }
+
+    pub fn union_with(&mut self, other: &BitGrid) {
+        assert_eq!((self.width, self.height), (other.width, other.height));
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a |= b;
+        }
+    }
+
+    pub fn intersect_with(&mut self, other: &BitGrid) {
+        assert_eq!((self.width, self.height), (other.width, other.height));
+        for (a, b) in self.words.iter_mut().zip(&other.words) {
+            *a &= b;
+        }
+    }
+
+    pub fn union(&self, other: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result.union_with(other);
+        result
+    }
+
+    pub fn intersection(&self, other: &BitGrid) -> BitGrid {
+        let mut result = self.clone();
+        result.intersect_with(other);
+        result
+    }
+
+    /// The points in the set, in row order.
+    pub fn iter(&self) -> impl Iterator<Item = Point> + '_ {
+        self.words.iter().enumerate().flat_map(move |(word, bits)| {
+            let mut bits = *bits;
+            std::iter::from_fn(move || {
+                if bits == 0 {
+                    return None;
+                }
+                let i = word * BITS + bits.trailing_zeros() as usize;
+                bits &= bits - 1;
+                Some(Point::new(i % self.width, i / self.width))
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_remove() {
+        let mut grid = BitGrid::new(3, 2);
+        assert!(grid.is_empty());
+        assert!(grid.insert(Point::new(2, 1)));
+        assert!(!grid.insert(Point::new(2, 1)));
+        assert!(grid.insert(Point::new(0, 0)));
+        assert_eq!(grid.count(), 2);
+
+        assert!(grid.remove(Point::new(2, 1)));
+        assert!(!grid.remove(Point::new(2, 1)));
+        assert!(!grid.contains(Point::new(2, 1)));
+        assert_eq!(grid.count(), 1);
+
+        grid.clear();
+        assert!(grid.is_empty());
+        assert_eq!(grid.count(), 0);
+    }
+
+    #[test]
+    fn test_iter_across_words() {
+        let mut grid = BitGrid::new(10, 13);
+        let points = [
+            Point::new(0, 0),
+            Point::new(3, 6),
+            Point::new(4, 6),
+            Point::new(7, 9),
+            Point::new(9, 12),
+        ];
+        for point in points.iter().rev() {
+            grid.insert(*point);
+        }
+        assert_eq!(grid.iter().collect::<Vec<_>>(), points);
+        assert_eq!(grid.count(), 5);
+    }
+
+    #[test]
+    fn test_union_intersection() {
+        let mut a = BitGrid::new(9, 9);
+        let mut b = BitGrid::new(9, 9);
+        for x in 0..9 {
+            a.insert(Point::new(x, 4));
+            b.insert(Point::new(4, x));
+        }
+
+        let union = a.union(&b);
+        assert_eq!(union.count(), 17);
+        assert!(union.contains(Point::new(0, 4)) && union.contains(Point::new(4, 8)));
+
+        let intersection = a.intersection(&b);
+        assert_eq!(intersection.iter().collect::<Vec<_>>(), [Point::new(4, 4)]);
+
+        a.intersect_with(&BitGrid::new(9, 9));
+        assert!(a.is_empty());
+    }
+}
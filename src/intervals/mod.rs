@@ -0,0 +1,501 @@
+use core::fmt;
+use std::any::type_name;
+
+use crate::{
+    parse::{CheckedParseable, NumberOverflow, Parseable, Sign},
+    point::traits::Integer,
+};
+
+/// The half-open range of integers from `start` (included) to `end`
+/// (excluded). An interval with `end <= start` is empty.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug, PartialOrd, Ord, Hash)]
+pub struct Interval<T> {
+    pub start: T,
+    pub end: T,
+}
+
+impl<T: fmt::Display> fmt::Display for Interval<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{},{})", self.start, self.end)
+    }
+}
+
+impl<T: Integer> Interval<T> {
+    pub fn new(start: T, end: T) -> Self {
+        Interval { start, end }
+    }
+
+    /// The interval covering `first..=last`.
+    ///
+    /// # Panics
+    ///
+    /// If `last` is the largest value of `T`, as the end would not fit. Use
+    /// [`checked_inclusive`](Interval::checked_inclusive) to handle that instead.
+    pub fn inclusive(first: T, last: T) -> Self {
+        Self::checked_inclusive(first, last).expect("interval end does not fit in its type")
+    }
+
+    /// The interval covering `first..=last`, or `None` if `last` is the
+    /// largest value of `T`.
+    pub fn checked_inclusive(first: T, last: T) -> Option<Self> {
+        Some(Interval::new(first, last.checked_add(T::ONE)?))
+    }
+
+    /// The interval of `len` values beginning at `start`.
+    pub fn with_len(start: T, len: T) -> Self {
+        Interval::new(start, start + len)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.end <= self.start
+    }
+
+    pub fn len(&self) -> T {
+        if self.is_empty() {
+            T::ZERO
+        } else {
+            self.end - self.start
+        }
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.start <= value && value < self.end
+    }
+
+    /// Whether `other` lies entirely within this interval.
+    pub fn contains_interval(&self, other: &Self) -> bool {
+        other.is_empty() || (self.start <= other.start && other.end <= self.end)
+    }
+
+    pub fn overlaps(&self, other: &Self) -> bool {
+        self.start.max(other.start) < self.end.min(other.end)
+    }
+
+    /// The overlap of both intervals, or `None` if they do not overlap.
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let overlap = Interval::new(self.start.max(other.start), self.end.min(other.end));
+        (!overlap.is_empty()).then_some(overlap)
+    }
+}
+
+/// Reads an inclusive range written as two numbers, such as `11-22`, and
+/// returns it as the half-open interval `[11,23)`. A range ending at the
+/// largest value of `T` is reported as an overflow, since its end does not fit.
+impl<I, T> CheckedParseable<Interval<T>> for I
+where
+    I: Iterator<Item = u8> + CheckedParseable<T>,
+    T: Integer,
+{
+    fn try_next_number_with(&mut self, sign: Sign) -> Result<Option<Interval<T>>, NumberOverflow> {
+        let Some(first) = CheckedParseable::<T>::try_next_number_with(self, sign)? else {
+            return Ok(None);
+        };
+        let Some(last) = CheckedParseable::<T>::try_next_number_with(self, sign)? else {
+            return Ok(None);
+        };
+        Interval::checked_inclusive(first, last)
+            .map(Some)
+            .ok_or(NumberOverflow {
+                type_name: type_name::<T>(),
+            })
+    }
+}
+
+impl<I, T> Parseable<Interval<T>> for I
+where
+    I: Iterator<Item = u8> + CheckedParseable<T>,
+    T: Integer,
+{
+    fn next_number(&mut self) -> Option<Interval<T>> {
+        CheckedParseable::<Interval<T>>::try_next_number(self).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+/// A set of integers stored as sorted, disjoint intervals. Touching or
+/// overlapping intervals are merged as they are inserted, so two sets with
+/// the same members always compare equal.
+#[derive(Default, Clone, PartialEq, Eq, Debug, Hash)]
+pub struct IntervalSet<T> {
+    intervals: Vec<Interval<T>>,
+}
+
+impl<T: fmt::Display> fmt::Display for IntervalSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{{")?;
+        for (i, interval) in self.intervals.iter().enumerate() {
+            if i > 0 {
+                write!(f, ", ")?;
+            }
+            write!(f, "{}", interval)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+impl<T: Integer> IntervalSet<T> {
+    pub fn new() -> Self {
+        IntervalSet {
+            intervals: Vec::new(),
+        }
+    }
+
+    /// The disjoint intervals in ascending order.
+    pub fn intervals(&self) -> &[Interval<T>] {
+        &self.intervals
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, Interval<T>> {
+        self.intervals.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.intervals.is_empty()
+    }
+
+    /// The number of integers in the set.
+    pub fn len(&self) -> T {
+        self.intervals
+            .iter()
+            .fold(T::ZERO, |sum, interval| sum + interval.len())
+    }
+
+    /// The first interval that ends after `value`, which is the only one
+    /// that could contain it.
+    fn first_ending_after(&self, value: T) -> usize {
+        self.intervals.partition_point(|interval| interval.end <= value)
+    }
+
+    pub fn contains(&self, value: T) -> bool {
+        self.intervals
+            .get(self.first_ending_after(value))
+            .is_some_and(|interval| interval.contains(value))
+    }
+
+    /// Whether every member of `interval` is in the set.
+    pub fn contains_interval(&self, interval: &Interval<T>) -> bool {
+        interval.is_empty()
+            || self
+                .intervals
+                .get(self.first_ending_after(interval.start))
+                .is_some_and(|found| found.contains_interval(interval))
+    }
+
+    pub fn insert(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+        // Everything from `first` up to `last` touches the new interval.
+        let first = self
+            .intervals
+            .partition_point(|existing| existing.end < interval.start);
+        let last = self
+            .intervals
+            .partition_point(|existing| existing.start <= interval.end);
+        let mut merged = interval;
+        if first < last {
+            merged.start = merged.start.min(self.intervals[first].start);
+            merged.end = merged.end.max(self.intervals[last - 1].end);
+        }
+        self.intervals.splice(first..last, [merged]);
+    }
+
+    pub fn remove(&mut self, interval: Interval<T>) {
+        if interval.is_empty() {
+            return;
+        }
+        let first = self.first_ending_after(interval.start);
+        let last = self
+            .intervals
+            .partition_point(|existing| existing.start < interval.end);
+        if first >= last {
+            return;
+        }
+        let before = Interval::new(self.intervals[first].start, interval.start);
+        let after = Interval::new(interval.end, self.intervals[last - 1].end);
+        let kept = [before, after].into_iter().filter(|part| !part.is_empty());
+        self.intervals.splice(first..last, kept);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for interval in &other.intervals {
+            result.insert(*interval);
+        }
+        result
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut intervals = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.intervals.len() && j < other.intervals.len() {
+            let (a, b) = (self.intervals[i], other.intervals[j]);
+            if let Some(overlap) = a.intersection(&b) {
+                intervals.push(overlap);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { intervals }
+    }
+
+    /// The members of this set that are not in `other`.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = self.clone();
+        for interval in &other.intervals {
+            result.remove(*interval);
+        }
+        result
+    }
+
+    /// Sends every member through the first rule whose source contains it,
+    /// leaving members that no rule covers unchanged.
+    pub fn map(&self, rules: &[OffsetRule<T>]) -> Self {
+        let mut remaining = self.clone();
+        let mut result = IntervalSet::new();
+        for rule in rules {
+            for part in remaining.intersection(&IntervalSet::from(rule.source)).iter() {
+                result.insert(rule.apply_interval(*part));
+            }
+            remaining.remove(rule.source);
+        }
+        result.union(&remaining)
+    }
+}
+
+impl<T: Integer> From<Interval<T>> for IntervalSet<T> {
+    fn from(value: Interval<T>) -> Self {
+        let mut set = IntervalSet::new();
+        set.insert(value);
+        set
+    }
+}
+
+impl<T: Integer> FromIterator<Interval<T>> for IntervalSet<T> {
+    fn from_iter<I: IntoIterator<Item = Interval<T>>>(iter: I) -> Self {
+        let mut set = IntervalSet::new();
+        for interval in iter {
+            set.insert(interval);
+        }
+        set
+    }
+}
+
+impl<T: Integer> Extend<Interval<T>> for IntervalSet<T> {
+    fn extend<I: IntoIterator<Item = Interval<T>>>(&mut self, iter: I) {
+        for interval in iter {
+            self.insert(interval);
+        }
+    }
+}
+
+impl<'a, T> IntoIterator for &'a IntervalSet<T> {
+    type Item = &'a Interval<T>;
+    type IntoIter = std::slice::Iter<'a, Interval<T>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.intervals.iter()
+    }
+}
+
+/// Moves the members of `source` so that `source.start` lands on
+/// `destination`, keeping their order.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub struct OffsetRule<T> {
+    pub source: Interval<T>,
+    pub destination: T,
+}
+
+impl<T: Integer> OffsetRule<T> {
+    pub fn new(source: Interval<T>, destination: T) -> Self {
+        OffsetRule {
+            source,
+            destination,
+        }
+    }
+
+    /// Where `value` is sent, or `None` if the rule does not cover it.
+    pub fn apply(&self, value: T) -> Option<T> {
+        self.source
+            .contains(value)
+            .then(|| value - self.source.start + self.destination)
+    }
+
+    /// Sends an interval lying within the source.
+    fn apply_interval(&self, interval: Interval<T>) -> Interval<T> {
+        Interval::with_len(
+            interval.start - self.source.start + self.destination,
+            interval.len(),
+        )
+    }
+}
+
+/// Sends `value` through the first rule that covers it, or returns it
+/// unchanged.
+pub fn map_value<T: Integer>(value: T, rules: &[OffsetRule<T>]) -> T {
+    rules
+        .iter()
+        .find_map(|rule| rule.apply(value))
+        .unwrap_or(value)
+}
+
+/// Reads a rule written as `destination source length`.
+impl<I, T> Parseable<OffsetRule<T>> for I
+where
+    I: Iterator<Item = u8> + Parseable<T>,
+    T: Integer,
+{
+    fn next_number(&mut self) -> Option<OffsetRule<T>> {
+        let destination = Parseable::<T>::next_number(self)?;
+        let start = Parseable::<T>::next_number(self)?;
+        let len = Parseable::<T>::next_number(self)?;
+        Some(OffsetRule::new(Interval::with_len(start, len), destination))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter::from_fn;
+
+    use super::*;
+
+    fn set(intervals: &[(i64, i64)]) -> IntervalSet<i64> {
+        intervals.iter().map(|&(s, e)| Interval::new(s, e)).collect()
+    }
+
+    fn intervals(set: &IntervalSet<i64>) -> Vec<(i64, i64)> {
+        set.iter().map(|i| (i.start, i.end)).collect()
+    }
+
+    #[test]
+    fn test_interval() {
+        let a = Interval::inclusive(3, 7);
+        assert_eq!(a, Interval::new(3, 8));
+        assert_eq!(a, Interval::with_len(3, 5));
+        assert_eq!(a.len(), 5);
+        assert!(a.contains(7) && !a.contains(8));
+        assert!(!a.overlaps(&Interval::new(8, 10)));
+        assert_eq!(a.intersection(&Interval::new(6, 10)), Some(Interval::new(6, 8)));
+        assert_eq!(a.to_string(), "[3,8)");
+
+        let empty = Interval::new(5, 2);
+        assert!(empty.is_empty());
+        assert_eq!(empty.len(), 0);
+        assert!(a.contains_interval(&empty));
+        assert_eq!(a.intersection(&empty), None);
+
+        assert_eq!(Interval::checked_inclusive(3u8, 254), Some(Interval::new(3, 255)));
+        assert_eq!(Interval::checked_inclusive(3u8, 255), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "interval end does not fit in its type")]
+    fn test_inclusive_overflow() {
+        Interval::inclusive(0u8, u8::MAX);
+    }
+
+    #[test]
+    fn test_insert_merges() {
+        let mut s = set(&[(0, 2), (5, 7), (10, 12)]);
+        s.insert(Interval::new(2, 3));
+        assert_eq!(intervals(&s), [(0, 3), (5, 7), (10, 12)]);
+        s.insert(Interval::new(4, 5));
+        assert_eq!(intervals(&s), [(0, 3), (4, 7), (10, 12)]);
+        s.insert(Interval::new(6, 11));
+        assert_eq!(intervals(&s), [(0, 3), (4, 12)]);
+        s.insert(Interval::new(-5, 20));
+        assert_eq!(intervals(&s), [(-5, 20)]);
+        assert_eq!(s.len(), 25);
+
+        s.insert(Interval::new(30, 30));
+        assert_eq!(intervals(&s), [(-5, 20)]);
+        assert_eq!(set(&[(3, 1), (4, 4)]), IntervalSet::new());
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut s = set(&[(0, 10), (20, 30), (40, 50)]);
+        s.remove(Interval::new(4, 6));
+        assert_eq!(intervals(&s), [(0, 4), (6, 10), (20, 30), (40, 50)]);
+        s.remove(Interval::new(0, 1));
+        s.remove(Interval::new(29, 30));
+        assert_eq!(intervals(&s), [(1, 4), (6, 10), (20, 29), (40, 50)]);
+        s.remove(Interval::new(8, 45));
+        assert_eq!(intervals(&s), [(1, 4), (6, 8), (45, 50)]);
+        s.remove(Interval::new(10, 40));
+        s.remove(Interval::new(7, 3));
+        assert_eq!(intervals(&s), [(1, 4), (6, 8), (45, 50)]);
+        assert_eq!(s.len(), 10);
+
+        assert!(s.contains(7) && !s.contains(8) && !s.contains(0));
+        assert!(s.contains_interval(&Interval::new(45, 50)));
+        assert!(!s.contains_interval(&Interval::new(3, 7)));
+    }
+
+    #[test]
+    fn test_set_operations() {
+        let a = set(&[(0, 5), (10, 15)]);
+        let b = set(&[(3, 12), (20, 21)]);
+        assert_eq!(intervals(&a.union(&b)), [(0, 15), (20, 21)]);
+        assert_eq!(intervals(&a.intersection(&b)), [(3, 5), (10, 12)]);
+        assert_eq!(intervals(&a.difference(&b)), [(0, 3), (12, 15)]);
+        assert_eq!(intervals(&b.difference(&a)), [(5, 10), (20, 21)]);
+        assert!(a.intersection(&IntervalSet::new()).is_empty());
+        assert_eq!(a.to_string(), "{[0,5), [10,15)}");
+    }
+
+    #[test]
+    fn test_map() {
+        let rules = [
+            OffsetRule::new(Interval::new(10, 20), 100),
+            OffsetRule::new(Interval::new(15, 30), 200),
+        ];
+        assert_eq!(map_value(12, &rules), 102);
+        assert_eq!(map_value(17, &rules), 107);
+        assert_eq!(map_value(25, &rules), 210);
+        assert_eq!(map_value(5, &rules), 5);
+
+        // Overlapping rules only see what earlier rules left behind
+        let mapped = set(&[(5, 25), (40, 42)]).map(&rules);
+        assert_eq!(intervals(&mapped), [(5, 10), (40, 42), (100, 110), (205, 210)]);
+        assert_eq!(mapped.len(), 22);
+
+        let unmatched = set(&[(0, 3)]).map(&rules);
+        assert_eq!(intervals(&unmatched), [(0, 3)]);
+        assert_eq!(IntervalSet::new().map(&rules), IntervalSet::new());
+    }
+
+    #[test]
+    fn test_parse() {
+        let mut bytes = "50 98 2\n52 50 48\n".bytes();
+        let rules: Vec<OffsetRule<u64>> = from_fn(|| bytes.next_number()).collect();
+        assert_eq!(
+            rules,
+            [
+                OffsetRule::new(Interval::with_len(98, 2), 50),
+                OffsetRule::new(Interval::with_len(50, 48), 52)
+            ]
+        );
+        assert_eq!(rules[0].apply(99), Some(51));
+        assert_eq!(rules[0].apply(100), None);
+
+        let mut bytes = "11-22,95-115".bytes();
+        let ranges: Vec<Interval<u64>> = from_fn(|| bytes.next_number()).collect();
+        assert_eq!(ranges, [Interval::new(11, 23), Interval::new(95, 116)]);
+
+        let mut bytes = "1-254,7-255,3-4".bytes();
+        let ranges: Vec<Result<Option<Interval<u8>>, _>> =
+            (0..4).map(|_| bytes.try_next_number()).collect();
+        assert_eq!(
+            ranges,
+            [
+                Ok(Some(Interval::new(1, 255))),
+                Err(NumberOverflow { type_name: "u8" }),
+                Ok(Some(Interval::new(3, 5))),
+                Ok(None)
+            ]
+        );
+    }
+}
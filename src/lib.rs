@@ -1,6 +1,12 @@
+pub mod bitgrid;
+pub mod intervals;
 pub mod parse;
 pub mod point;
 pub mod pointmap;
+pub mod polygon;
+pub mod rect;
+pub mod search;
+pub mod sparsemap;
 pub mod template;
 pub mod math;
 
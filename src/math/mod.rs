@@ -32,3 +32,12 @@ pub fn extended_euclid(a: isize, b: isize) -> (isize, isize, isize) {
 
     (y1 - (b / a) * x1, x1, gcd)
 }
+
+/// The greatest common divisor, always non-negative. `gcd(0, 0)` is 0.
+pub fn gcd(a: isize, b: isize) -> isize {
+    let (mut a, mut b) = (a.abs(), b.abs());
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
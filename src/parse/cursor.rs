@@ -0,0 +1,346 @@
+use std::{any::type_name, error::Error, fmt::Display, str::FromStr};
+
+/// How much of the remaining input an error quotes.
+const SNIPPET_LEN: usize = 20;
+
+/// An error from a [`Cursor`], pointing at where in the input it happened.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: usize,
+    /// What the parser was looking for.
+    pub expected: String,
+    /// The start of the input at the error, up to the end of its line.
+    pub snippet: String,
+}
+
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found ",
+            self.line, self.column, self.expected
+        )?;
+        if self.snippet.is_empty() {
+            write!(f, "end of line")
+        } else {
+            write!(f, "{:?}", self.snippet)
+        }
+    }
+}
+
+/// A position in some input text, which parsing functions advance as they
+/// consume it. On failure the cursor is left where the mismatch was found.
+#[derive(Debug, Clone, Copy)]
+pub struct Cursor<'a> {
+    input: &'a str,
+    pos: usize,
+}
+
+impl<'a> Cursor<'a> {
+    pub fn new(input: &'a str) -> Self {
+        Cursor { input, pos: 0 }
+    }
+
+    /// The byte offset into the input.
+    pub fn pos(&self) -> usize {
+        self.pos
+    }
+
+    /// The input that has not been consumed yet.
+    pub fn rest(&self) -> &'a str {
+        &self.input[self.pos..]
+    }
+
+    pub fn is_at_end(&self) -> bool {
+        self.pos == self.input.len()
+    }
+
+    pub fn peek(&self) -> Option<u8> {
+        self.rest().bytes().next()
+    }
+
+    /// An error at the current position, saying what was `expected`.
+    pub fn error(&self, expected: impl Into<String>) -> ParseError {
+        let before = &self.input[..self.pos];
+        let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+        let rest_of_line = self.rest().lines().next().unwrap_or("");
+        ParseError {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            expected: expected.into(),
+            snippet: rest_of_line.chars().take(SNIPPET_LEN).collect(),
+        }
+    }
+
+    /// Consumes `literal` if the input continues with it.
+    pub fn eat_literal(&mut self, literal: &str) -> bool {
+        if self.rest().starts_with(literal) {
+            self.pos += literal.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn expect_literal(&mut self, literal: &str) -> Result<(), ParseError> {
+        if self.eat_literal(literal) {
+            Ok(())
+        } else {
+            Err(self.error(format!("{:?}", literal)))
+        }
+    }
+
+    /// Expects `text`, where each `\n` in it matches a line break in the
+    /// input along with any trailing spaces before it.
+    pub fn expect_text(&mut self, text: &str) -> Result<(), ParseError> {
+        for (i, part) in text.split('\n').enumerate() {
+            if i > 0 {
+                self.end_of_line()?;
+            }
+            self.expect_literal(part)?;
+        }
+        Ok(())
+    }
+
+    /// Consumes spaces and tabs, but not line breaks.
+    pub fn skip_spaces(&mut self) {
+        let spaces = self.rest().len() - self.rest().trim_start_matches([' ', '\t']).len();
+        self.pos += spaces;
+    }
+
+    /// Reads an integer with an optional leading `+` or `-`. Values that do
+    /// not fit in `T`, including negative values for unsigned types, are
+    /// reported as errors rather than wrapped.
+    pub fn number<T>(&mut self) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        let rest = self.rest().as_bytes();
+        let sign = usize::from(matches!(rest.first(), Some(b'+' | b'-')));
+        let digits = rest[sign..].iter().take_while(|b| b.is_ascii_digit()).count();
+        if digits == 0 {
+            return Err(self.error(format!("a number of type {}", type_name::<T>())));
+        }
+        let text = &self.rest()[..sign + digits];
+        let value = text
+            .parse()
+            .map_err(|e| self.error(format!("a number of type {} ({})", type_name::<T>(), e)))?;
+        self.pos += text.len();
+        Ok(value)
+    }
+
+    /// Parses one or more `item`s separated by `separator`.
+    pub fn sep_by<T, F>(&mut self, separator: &str, mut item: F) -> Result<Vec<T>, ParseError>
+    where
+        F: FnMut(&mut Self) -> Result<T, ParseError>,
+    {
+        let mut items = vec![item(self)?];
+        while self.eat_literal(separator) {
+            items.push(item(self)?);
+        }
+        Ok(items)
+    }
+
+    /// Consumes the rest of the current line, and its line break.
+    pub fn line(&mut self) -> &'a str {
+        let line = self.rest().lines().next().unwrap_or("");
+        self.pos += line.len();
+        self.eat_line_break();
+        line.strip_suffix('\r').unwrap_or(line)
+    }
+
+    fn eat_line_break(&mut self) -> bool {
+        self.eat_literal("\n") || self.eat_literal("\r\n")
+    }
+
+    /// Expects the current line to end here, allowing trailing spaces, and
+    /// moves to the start of the next.
+    pub fn end_of_line(&mut self) -> Result<(), ParseError> {
+        self.skip_spaces();
+        if self.eat_line_break() || self.is_at_end() {
+            Ok(())
+        } else {
+            Err(self.error("end of line"))
+        }
+    }
+
+    fn at_line_start(&self) -> bool {
+        self.pos == 0 || self.input[..self.pos].ends_with('\n')
+    }
+
+    /// Whether the cursor is at the start of a line holding only whitespace.
+    fn at_blank_line(&self) -> bool {
+        self.rest()
+            .split('\n')
+            .next()
+            .is_some_and(|line| line.trim().is_empty())
+    }
+
+    /// Parses `item` once per line until the end of the input or a blank
+    /// line, which is left unconsumed. Each `item` must use its whole line.
+    pub fn lines<T, F>(&mut self, mut item: F) -> Result<Vec<T>, ParseError>
+    where
+        F: FnMut(&mut Self) -> Result<T, ParseError>,
+    {
+        let mut items = Vec::new();
+        while !self.is_at_end() && !self.at_blank_line() {
+            items.push(item(self)?);
+            self.end_of_line()?;
+        }
+        Ok(items)
+    }
+
+    /// Expects one or more blank lines, as between sections of input. If
+    /// the cursor is partway through a line, that line must end first.
+    pub fn section_break(&mut self) -> Result<(), ParseError> {
+        if !self.at_line_start() {
+            self.end_of_line()?;
+        }
+        if self.is_at_end() || !self.at_blank_line() {
+            return Err(self.error("a blank line"));
+        }
+        while !self.is_at_end() && self.at_blank_line() {
+            self.line();
+        }
+        Ok(())
+    }
+
+    /// Parses `item` for each section of the input, where sections are
+    /// separated by blank lines.
+    pub fn sections<T, F>(&mut self, mut item: F) -> Result<Vec<T>, ParseError>
+    where
+        F: FnMut(&mut Self) -> Result<T, ParseError>,
+    {
+        let mut items = vec![item(self)?];
+        while !self.rest().trim().is_empty() {
+            self.section_break()?;
+            items.push(item(self)?);
+        }
+        Ok(items)
+    }
+
+    /// Expects nothing but whitespace to remain.
+    pub fn finish(&mut self) -> Result<(), ParseError> {
+        let trailing = self.rest().len() - self.rest().trim_start().len();
+        if trailing == self.rest().len() {
+            self.pos = self.input.len();
+            Ok(())
+        } else {
+            self.pos += trailing;
+            Err(self.error("end of input"))
+        }
+    }
+}
+
+/// Runs `parser` over the whole of `input`, failing if anything but
+/// whitespace is left over.
+pub fn parse_all<'a, T, F>(input: &'a str, parser: F) -> Result<T, ParseError>
+where
+    F: FnOnce(&mut Cursor<'a>) -> Result<T, ParseError>,
+{
+    let mut cursor = Cursor::new(input);
+    let value = parser(&mut cursor)?;
+    cursor.finish()?;
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_position() {
+        let mut cursor = Cursor::new("first line\nsecond: x\nthird");
+        cursor.line();
+        cursor.expect_literal("second: ").unwrap();
+        let error = cursor.expect_literal("42").unwrap_err();
+        assert_eq!((error.line, error.column), (2, 9));
+        assert_eq!(error.expected, "\"42\"");
+        assert_eq!(error.snippet, "x");
+        assert_eq!(error.to_string(), "line 2, column 9: expected \"42\", found \"x\"");
+
+        // Columns count characters rather than bytes
+        let mut cursor = Cursor::new("é: ?");
+        cursor.expect_literal("é: ").unwrap();
+        assert_eq!(cursor.error("a digit").column, 4);
+
+        let mut cursor = Cursor::new("a\n");
+        cursor.line();
+        let error = cursor.error("more");
+        assert_eq!((error.line, error.column), (2, 1));
+        assert!(error.to_string().ends_with("found end of line"));
+    }
+
+    #[test]
+    fn test_number() {
+        let mut cursor = Cursor::new("+12 -7 255");
+        assert_eq!(cursor.number::<i32>(), Ok(12));
+        cursor.skip_spaces();
+        assert_eq!(cursor.number::<i32>(), Ok(-7));
+        cursor.skip_spaces();
+        assert_eq!(cursor.number::<u8>(), Ok(255));
+        assert!(cursor.is_at_end());
+
+        for input in ["-1", "256", "x", "-"] {
+            let mut cursor = Cursor::new(input);
+            let error = cursor.number::<u8>().unwrap_err();
+            assert!(error.expected.starts_with("a number of type u8"), "{}", input);
+            assert_eq!(error.column, 1);
+            assert_eq!(cursor.pos(), 0);
+        }
+    }
+
+    #[test]
+    fn test_crlf() {
+        let mut cursor = Cursor::new("one\r\ntwo  \r\nthree");
+        assert_eq!(cursor.line(), "one");
+        cursor.expect_literal("two").unwrap();
+        cursor.end_of_line().unwrap();
+        assert_eq!(cursor.line(), "three");
+        assert!(cursor.is_at_end());
+        assert_eq!(cursor.line(), "");
+
+        let mut cursor = Cursor::new("a: 1\r\nb: 2\r\n");
+        cursor.expect_text("a: 1\nb: ").unwrap();
+        assert_eq!(cursor.number::<u8>(), Ok(2));
+        cursor.finish().unwrap();
+
+        let mut cursor = Cursor::new("1 x\r\n");
+        cursor.number::<u8>().unwrap();
+        let error = cursor.end_of_line().unwrap_err();
+        assert_eq!((error.column, error.snippet.as_str()), (3, "x"));
+    }
+
+    #[test]
+    fn test_sections() {
+        let input = "1\n2\n\n\n  \n3\r\n\r\n\r\n4\n5\n\n";
+        let sections = parse_all(input, |c| c.sections(|c| c.lines(|c| c.number::<u8>())));
+        assert_eq!(sections, Ok(vec![vec![1, 2], vec![3], vec![4, 5]]));
+
+        let error = parse_all("1\n2\n3", |c| {
+            let first = c.lines(|c| c.number::<u8>())?;
+            c.section_break()?;
+            Ok(first)
+        });
+        assert_eq!(error.map_err(|e| (e.line, e.expected)), Err((3, "a blank line".into())));
+
+        let error = parse_all("1\n\nx\n", |c| c.sections(|c| c.lines(|c| c.number::<u8>())));
+        assert_eq!(error.map_err(|e| (e.line, e.column)), Err((3, 1)));
+    }
+
+    #[test]
+    fn test_sep_by_and_finish() {
+        let mut cursor = Cursor::new("1,2,3;");
+        assert_eq!(cursor.sep_by(",", |c| c.number::<u8>()), Ok(vec![1, 2, 3]));
+        assert_eq!(cursor.peek(), Some(b';'));
+        assert_eq!(cursor.finish().unwrap_err().expected, "end of input");
+
+        assert_eq!(parse_all("7 \n\n", |c| c.number::<u8>()), Ok(7));
+    }
+}
@@ -0,0 +1,304 @@
+use std::{any::type_name, iter::Peekable, marker::PhantomData};
+
+use crate::point::traits::Integer;
+
+use super::{NumberOverflow, Sign, ToDigit};
+
+/// Iterator adaptors over bytes for number formats other than the plain
+/// base-10 integers read by [`Parseable`](super::Parseable).
+pub trait NumberFormats: Iterator<Item = u8> + Sized {
+    /// Each decimal digit on its own, for inputs like `2333133121414131402`
+    /// where digits are not separated. Other bytes are skipped.
+    fn digits(self) -> Digits<Self> {
+        Digits { iter: self }
+    }
+
+    /// Integers written in base `radix`, skipping bytes between them. A
+    /// `0x`, `0o` or `0b` prefix matching the radix is allowed when a digit
+    /// follows it. Signs are not recognised.
+    ///
+    /// # Panics
+    ///
+    /// When iterated, if a number does not fit in `T`. Use
+    /// [`try_radix_numbers`](NumberFormats::try_radix_numbers) to handle
+    /// that instead.
+    fn radix_numbers<T>(self, radix: u32) -> RadixNumbers<Self, T> {
+        assert!((2..=36).contains(&radix), "radix must be between 2 and 36");
+        RadixNumbers {
+            iter: self.peekable(),
+            radix,
+            number: PhantomData,
+        }
+    }
+
+    /// Like [`radix_numbers`](NumberFormats::radix_numbers), but yielding
+    /// an error for each number that does not fit in `T`. The whole of an
+    /// overflowing number is still consumed, so parsing can carry on after it.
+    fn try_radix_numbers<T>(self, radix: u32) -> TryRadixNumbers<Self, T> {
+        TryRadixNumbers(self.radix_numbers(radix))
+    }
+
+    fn hex_numbers<T>(self) -> RadixNumbers<Self, T> {
+        self.radix_numbers(16)
+    }
+
+    fn octal_numbers<T>(self) -> RadixNumbers<Self, T> {
+        self.radix_numbers(8)
+    }
+
+    fn binary_numbers<T>(self) -> RadixNumbers<Self, T> {
+        self.radix_numbers(2)
+    }
+
+    /// Decimal numbers with an optional fractional part, such as `-12.5` or
+    /// `.25`. A `-` immediately before the number makes it negative, unless
+    /// it directly follows the previous number as in the range `1.5-2.5`.
+    fn floats(self) -> Floats<Self> {
+        self.floats_with(Sign::Adjacent)
+    }
+
+    /// Like [`floats`](NumberFormats::floats), treating a `-` as `sign` says.
+    fn floats_with(self, sign: Sign) -> Floats<Self> {
+        Floats {
+            iter: self.peekable(),
+            sign,
+            after_number: false,
+        }
+    }
+}
+
+impl<I: Iterator<Item = u8>> NumberFormats for I {}
+
+pub struct Digits<I> {
+    iter: I,
+}
+
+impl<I: Iterator<Item = u8>> Iterator for Digits<I> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.by_ref().find_map(|byte| byte.to_digit())
+    }
+}
+
+pub struct RadixNumbers<I: Iterator, T> {
+    iter: Peekable<I>,
+    radix: u32,
+    number: PhantomData<T>,
+}
+
+impl<I, T> RadixNumbers<I, T>
+where
+    I: Iterator<Item = u8>,
+    T: Integer + TryFrom<u8>,
+{
+    fn try_next(&mut self) -> Option<Result<T, NumberOverflow>> {
+        let prefix = match self.radix {
+            16 => Some(b'x'),
+            8 => Some(b'o'),
+            2 => Some(b'b'),
+            _ => None,
+        };
+        // Every integer type holds the digits of a radix up to 36
+        let cast = |value: u8| T::try_from(value).ok().expect("digit fits in any integer type");
+        let radix = cast(self.radix as u8);
+
+        let mut value: Option<T> = None;
+        let mut overflow = false;
+        let mut leading_zero = false;
+        while let Some(byte) = self.iter.next() {
+            if let Some(digit) = byte.to_digit_radix(self.radix) {
+                let current = value.unwrap_or(T::ZERO);
+                leading_zero = value.is_none() && digit == 0;
+                match current.checked_mul(radix).and_then(|v| v.checked_add(cast(digit))) {
+                    Some(next) if !overflow => value = Some(next),
+                    _ => {
+                        overflow = true;
+                        value = Some(current);
+                    }
+                }
+            } else if leading_zero
+                && Some(byte.to_ascii_lowercase()) == prefix
+                && self
+                    .iter
+                    .peek()
+                    .is_some_and(|next| next.to_digit_radix(self.radix).is_some())
+            {
+                value = None;
+                leading_zero = false;
+            } else if value.is_some() {
+                break;
+            }
+        }
+
+        if overflow {
+            return Some(Err(NumberOverflow {
+                type_name: type_name::<T>(),
+            }));
+        }
+        value.map(Ok)
+    }
+}
+
+impl<I, T> Iterator for RadixNumbers<I, T>
+where
+    I: Iterator<Item = u8>,
+    T: Integer + TryFrom<u8>,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.try_next()
+            .map(|result| result.unwrap_or_else(|e| panic!("{}", e)))
+    }
+}
+
+pub struct TryRadixNumbers<I: Iterator, T>(RadixNumbers<I, T>);
+
+impl<I, T> Iterator for TryRadixNumbers<I, T>
+where
+    I: Iterator<Item = u8>,
+    T: Integer + TryFrom<u8>,
+{
+    type Item = Result<T, NumberOverflow>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.0.try_next()
+    }
+}
+
+pub struct Floats<I: Iterator> {
+    iter: Peekable<I>,
+    sign: Sign,
+    /// Whether the last number ended right before the next byte.
+    after_number: bool,
+}
+
+impl<I: Iterator<Item = u8>> Iterator for Floats<I> {
+    type Item = f64;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut negative = false;
+        let mut fraction = false;
+        // A `-` joining two numbers separates them rather than signing the second
+        if std::mem::take(&mut self.after_number) && self.iter.peek() == Some(&b'-') {
+            self.iter.next();
+        }
+        loop {
+            match *self.iter.peek()? {
+                byte if byte.is_ascii_digit() => break,
+                b'-' => negative = self.sign == Sign::Adjacent,
+                b'.' => {
+                    // A point directly before a digit starts the number
+                    self.iter.next();
+                    if self.iter.peek().is_some_and(u8::is_ascii_digit) {
+                        fraction = true;
+                        break;
+                    }
+                    negative = false;
+                    continue;
+                }
+                _ => negative = false,
+            }
+            self.iter.next();
+        }
+
+        let mut text = String::from(if negative { "-" } else { "" });
+        if fraction {
+            text.push('.');
+        }
+        while let Some(&byte) = self.iter.peek() {
+            if byte.is_ascii_digit() {
+                text.push(byte as char);
+            } else if byte == b'.' && !fraction {
+                fraction = true;
+                text.push('.');
+            } else {
+                break;
+            }
+            self.iter.next();
+        }
+
+        self.after_number = true;
+        text.parse().ok()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digits() {
+        let digits: Vec<u8> = "2333\n1x3".bytes().digits().collect();
+        assert_eq!(digits, [2, 3, 3, 3, 1, 3]);
+    }
+
+    #[test]
+    fn test_radix_numbers() {
+        let hex: Vec<u32> = "#ff00A0 0x1F, 0".bytes().hex_numbers().collect();
+        assert_eq!(hex, [0xff00a0, 0x1f, 0]);
+        let binary: Vec<u8> = "0b101 11111111".bytes().binary_numbers().collect();
+        assert_eq!(binary, [5, 255]);
+        let octal: Vec<i16> = "0o17 755".bytes().octal_numbers().collect();
+        assert_eq!(octal, [15, 0o755]);
+
+        let base36: Vec<u64> = "zz, Hello 0 10".bytes().radix_numbers(36).collect();
+        assert_eq!(base36, [1295, 29_234_652, 0, 36]);
+        let base3: Vec<u8> = "1203 21".bytes().radix_numbers(3).collect();
+        assert_eq!(base3, [15, 7]);
+    }
+
+    #[test]
+    fn test_radix_prefix() {
+        let hex: Vec<u8> = "0x 0xg 7 0X0f".bytes().hex_numbers().collect();
+        assert_eq!(hex, [0, 0, 7, 15]);
+        let binary: Vec<u8> = "0b2 0b 0b11".bytes().binary_numbers().collect();
+        assert_eq!(binary, [0, 0, 3]);
+    }
+
+    #[test]
+    fn test_try_radix_numbers() {
+        let overflow = Err(NumberOverflow { type_name: "u8" });
+        let hex: Vec<_> = "ff 100 7 fff0".bytes().try_radix_numbers::<u8>(16).collect();
+        assert_eq!(hex, [Ok(255), overflow, Ok(7), overflow]);
+
+        let overflow = Err(NumberOverflow { type_name: "i8" });
+        let binary: Vec<_> = "1111111 10000000,1".bytes().try_radix_numbers::<i8>(2).collect();
+        assert_eq!(binary, [Ok(127), overflow, Ok(1)]);
+    }
+
+    #[test]
+    #[should_panic(expected = "number does not fit in u8")]
+    fn test_radix_overflow() {
+        "ff 100".bytes().hex_numbers::<u8>().for_each(drop);
+    }
+
+    #[test]
+    #[should_panic(expected = "number does not fit in i8")]
+    fn test_radix_overflow_signed() {
+        "1111111 10000000".bytes().binary_numbers::<i8>().for_each(drop);
+    }
+
+    #[test]
+    #[should_panic(expected = "radix must be between 2 and 36")]
+    fn test_invalid_radix() {
+        "1".bytes().radix_numbers::<u32>(37);
+    }
+
+    #[test]
+    fn test_floats() {
+        let floats: Vec<f64> = "x=-12.5, y=3. z=.25 w=7".bytes().floats().collect();
+        assert_eq!(floats, [-12.5, 3.0, 0.25, 7.0]);
+        let floats: Vec<f64> = "-.5 - 1 .. 2.5.5 a.b".bytes().floats().collect();
+        assert_eq!(floats, [-0.5, 1.0, 2.5, 0.5]);
+    }
+
+    #[test]
+    fn test_float_ranges() {
+        let floats: Vec<f64> = "1.5-2.5 -3--1.25 4.-5".bytes().floats().collect();
+        assert_eq!(floats, [1.5, 2.5, -3.0, -1.25, 4.0, 5.0]);
+        let floats: Vec<f64> = "x=-1.5 y=-2".bytes().floats_with(Sign::Never).collect();
+        assert_eq!(floats, [1.5, 2.0]);
+    }
+}
@@ -1,5 +1,18 @@
+pub mod cursor;
+pub mod formats;
+mod record;
+mod sections;
+
+pub use sections::{sections, sections2, sections3, SectionError};
+
+use std::{error::Error, fmt::Display};
+
 pub trait ToDigit {
     fn to_digit(&self) -> Option<u8>;
+
+    /// The value of this digit in base `radix`, using letters for digits
+    /// above 9 regardless of case.
+    fn to_digit_radix(&self, radix: u32) -> Option<u8>;
 }
 
 impl ToDigit for u8 {
@@ -9,30 +22,86 @@ impl ToDigit for u8 {
         }
         None
     }
+
+    fn to_digit_radix(&self, radix: u32) -> Option<u8> {
+        (*self as char).to_digit(radix).map(|digit| digit as u8)
+    }
 }
 
 pub trait Parseable<T>: Iterator {
     fn next_number(&mut self) -> Option<T>;
 }
 
+/// How a `-` in front of a number is treated when parsing signed types.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Sign {
+    /// A `-` immediately before the first digit makes the number negative.
+    #[default]
+    Adjacent,
+    /// A `-` is never a sign, so `x=-3` reads as `3`.
+    Never,
+}
+
+/// The error returned when a number has too many digits for its type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberOverflow {
+    pub type_name: &'static str,
+}
+
+impl Error for NumberOverflow {}
+
+impl Display for NumberOverflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "number does not fit in {}", self.type_name)
+    }
+}
+
+/// Like [`Parseable`], but reporting numbers that overflow instead of
+/// panicking. The whole of an overflowing number is still consumed, so
+/// parsing can carry on after it.
+pub trait CheckedParseable<T>: Iterator {
+    /// The next number, or `Ok(None)` once the input runs out.
+    fn try_next_number(&mut self) -> Result<Option<T>, NumberOverflow> {
+        self.try_next_number_with(Sign::Adjacent)
+    }
+
+    /// The next number, treating a leading `-` as `sign` says.
+    fn try_next_number_with(&mut self, sign: Sign) -> Result<Option<T>, NumberOverflow>;
+}
+
 macro_rules! Parseable_number {
     ($type:ident) => {
-        impl<T: Iterator<Item = u8>> Parseable<$type> for T {
-            fn next_number(&mut self) -> Option<$type> {
+        impl<T: Iterator<Item = u8>> CheckedParseable<$type> for T {
+            fn try_next_number_with(&mut self, _sign: Sign) -> Result<Option<$type>, NumberOverflow> {
                 let mut value: Option<$type> = None;
+                let mut overflow = false;
                 for byte in self {
                     if let Some(digit) = byte.to_digit() {
-                        if let Some(current) = value {
-                            value = Some(current * 10 + digit as $type);
-                        } else {
-                            value = Some(digit as $type);
+                        let current = value.unwrap_or(0);
+                        match current.checked_mul(10).and_then(|v| v.checked_add(digit as $type)) {
+                            Some(next) if !overflow => value = Some(next),
+                            _ => {
+                                overflow = true;
+                                value = Some(current);
+                            }
                         }
                     } else if value.is_some() {
-                        return value;
+                        break;
                     }
                 }
 
-                value
+                if overflow {
+                    return Err(NumberOverflow {
+                        type_name: stringify!($type),
+                    });
+                }
+                Ok(value)
+            }
+        }
+
+        impl<T: Iterator<Item = u8>> Parseable<$type> for T {
+            fn next_number(&mut self) -> Option<$type> {
+                CheckedParseable::<$type>::try_next_number(self).unwrap_or_else(|e| panic!("{}", e))
             }
         }
     };
@@ -40,36 +109,49 @@ macro_rules! Parseable_number {
 
 macro_rules! Parseable_signed_number {
     ($type:ident) => {
-        impl<T: Iterator<Item = u8>> Parseable<$type> for T {
-            fn next_number(&mut self) -> Option<$type> {
+        impl<T: Iterator<Item = u8>> CheckedParseable<$type> for T {
+            fn try_next_number_with(&mut self, sign: Sign) -> Result<Option<$type>, NumberOverflow> {
                 let mut negative = false;
                 let mut value: Option<$type> = None;
+                let mut overflow = false;
                 for byte in self {
                     if let Some(digit) = byte.to_digit() {
-                        if let Some(current) = value {
-                            value = Some(current * 10 + digit as $type);
-                        } else {
-                            value = Some(digit as $type);
-                        }
-                    } else if let Some(value) = value {
-                        if negative {
-                            return Some(-value);
+                        // Negative numbers are built downwards so that MIN fits.
+                        let digit = digit as $type;
+                        let current = value.unwrap_or(0);
+                        let next = current.checked_mul(10).and_then(|v| {
+                            if negative {
+                                v.checked_sub(digit)
+                            } else {
+                                v.checked_add(digit)
+                            }
+                        });
+                        match next {
+                            Some(next) if !overflow => value = Some(next),
+                            _ => {
+                                overflow = true;
+                                value = Some(current);
+                            }
                         }
-                        return Some(value);
-                    } else if byte == b'-' {
-                        negative = true;
+                    } else if value.is_some() {
+                        break;
                     } else {
-                        negative = false;
+                        negative = byte == b'-' && sign == Sign::Adjacent;
                     }
                 }
 
-                if let Some(value) = value {
-                    if negative {
-                        return Some(-value);
-                    }
-                    return Some(value);
+                if overflow {
+                    return Err(NumberOverflow {
+                        type_name: stringify!($type),
+                    });
                 }
-                None
+                Ok(value)
+            }
+        }
+
+        impl<T: Iterator<Item = u8>> Parseable<$type> for T {
+            fn next_number(&mut self) -> Option<$type> {
+                CheckedParseable::<$type>::try_next_number(self).unwrap_or_else(|e| panic!("{}", e))
             }
         }
     };
@@ -87,3 +169,80 @@ Parseable_number!(u128);
 Parseable_signed_number!(i128);
 Parseable_number!(usize);
 Parseable_signed_number!(isize);
+
+#[cfg(test)]
+mod tests {
+    use std::iter::from_fn;
+
+    use super::*;
+    use crate::intervals::Interval;
+
+    fn numbers<'a, T>(input: &'a str, sign: Sign) -> Vec<Result<T, NumberOverflow>>
+    where
+        std::str::Bytes<'a>: CheckedParseable<T>,
+    {
+        let mut bytes = input.bytes();
+        from_fn(|| bytes.try_next_number_with(sign).transpose()).collect()
+    }
+
+    #[test]
+    fn test_ranges() {
+        assert_eq!(numbers::<i32>("3-7", Sign::Adjacent), [Ok(3), Ok(7)]);
+        assert_eq!(numbers::<i32>("3-7", Sign::Never), [Ok(3), Ok(7)]);
+        assert_eq!(numbers::<u32>("3-7,10-20", Sign::Adjacent), [Ok(3), Ok(7), Ok(10), Ok(20)]);
+        assert_eq!(numbers::<i64>("x=-3..-1", Sign::Adjacent), [Ok(-3), Ok(-1)]);
+        assert_eq!(numbers::<i64>("x=-3..-1", Sign::Never), [Ok(3), Ok(1)]);
+        assert_eq!(numbers::<i8>("- 5 -6", Sign::Adjacent), [Ok(5), Ok(-6)]);
+
+        let mut bytes = "11-22,95-115".bytes();
+        let ranges: Vec<Interval<i64>> = from_fn(|| bytes.next_number()).collect();
+        assert_eq!(ranges, [Interval::new(11, 23), Interval::new(95, 116)]);
+    }
+
+    macro_rules! test_limits {
+        ($name:ident, $type:ident) => {
+            #[test]
+            fn $name() {
+                let input = format!("{} {}", $type::MAX, $type::MIN);
+                assert_eq!(
+                    numbers::<$type>(&input, Sign::Adjacent),
+                    [Ok($type::MAX), Ok($type::MIN)]
+                );
+
+                let overflow = Err(NumberOverflow {
+                    type_name: stringify!($type),
+                });
+                let input = format!("{}0,7", $type::MAX);
+                assert_eq!(numbers::<$type>(&input, Sign::Adjacent), [overflow, Ok(7)]);
+                let input = format!("{}", $type::MAX as u128 + 1);
+                assert_eq!(numbers::<$type>(&input, Sign::Adjacent), [overflow]);
+            }
+        };
+    }
+
+    test_limits!(test_limits_u8, u8);
+    test_limits!(test_limits_i8, i8);
+    test_limits!(test_limits_u16, u16);
+    test_limits!(test_limits_i16, i16);
+    test_limits!(test_limits_u32, u32);
+    test_limits!(test_limits_i32, i32);
+    test_limits!(test_limits_u64, u64);
+    test_limits!(test_limits_i64, i64);
+    test_limits!(test_limits_i128, i128);
+
+    #[test]
+    fn test_limits_u128() {
+        let input = format!("{} 0", u128::MAX);
+        assert_eq!(numbers::<u128>(&input, Sign::Adjacent), [Ok(u128::MAX), Ok(0)]);
+        let input = format!("{}0,7", u128::MAX);
+        let overflow = Err(NumberOverflow { type_name: "u128" });
+        assert_eq!(numbers::<u128>(&input, Sign::Adjacent), [overflow, Ok(7)]);
+    }
+
+    #[test]
+    fn test_negative_overflow() {
+        let overflow = Err(NumberOverflow { type_name: "i8" });
+        assert_eq!(numbers::<i8>("-129 -128", Sign::Adjacent), [overflow, Ok(-128)]);
+        assert_eq!(numbers::<i8>("-128", Sign::Never), [overflow]);
+    }
+}
@@ -0,0 +1,128 @@
+/// Declares a struct along with a parser for it, described by a template
+/// of literal text and `name: type` placeholders in the order they appear
+/// in the input. Each placeholder becomes a field read with
+/// [`Cursor::number`](crate::parse::cursor::Cursor::number), and each `\n`
+/// in the literal text matches any line break.
+///
+/// The struct gains `parse_record`, which reads a single record from a
+/// [`Cursor`](crate::parse::cursor::Cursor), and `parse_records`, which
+/// reads a whole input of records separated by blank lines.
+///
+/// # Example
+///
+/// ```ignore
+/// advent_of_code::parse_record! {
+///     #[derive(Debug)]
+///     struct Machine {
+///         "Button A: X+", ax: i64, ", Y+", ay: i64, "\n",
+///         "Prize: X=", px: i64, ", Y=", py: i64,
+///     }
+/// }
+///
+/// let machines = Machine::parse_records(input)?;
+/// ```
+#[macro_export]
+macro_rules! parse_record {
+    ($(#[$meta:meta])* $vis:vis struct $name:ident { $($template:tt)* }) => {
+        $crate::parse_record!(@munch [$(#[$meta])* $vis struct $name] [] [] $($template)*);
+    };
+
+    (@munch $head:tt [$($fields:tt)*] [$($steps:tt)*] $text:literal $(, $($rest:tt)*)?) => {
+        $crate::parse_record!(@munch $head [$($fields)*] [$($steps)* (text $text)] $($($rest)*)?);
+    };
+    (@munch $head:tt [$($fields:tt)*] [$($steps:tt)*] $field:ident : $type:ty $(, $($rest:tt)*)?) => {
+        $crate::parse_record!(
+            @munch $head [$($fields)* ($field $type)] [$($steps)* (field $field $type)] $($($rest)*)?
+        );
+    };
+
+    (@munch [$(#[$meta:meta])* $vis:vis struct $name:ident] [$(($field:ident $type:ty))*] [$($step:tt)*]) => {
+        $(#[$meta])*
+        $vis struct $name {
+            $($vis $field: $type,)*
+        }
+
+        impl $name {
+            /// Reads one record, leaving the cursor just after it.
+            #[allow(dead_code)]
+            $vis fn parse_record(
+                cursor: &mut $crate::parse::cursor::Cursor,
+            ) -> Result<Self, $crate::parse::cursor::ParseError> {
+                $($crate::parse_record!(@step cursor $step);)*
+                Ok($name { $($field),* })
+            }
+
+            /// Reads every record in `input`, separated by blank lines.
+            #[allow(dead_code)]
+            $vis fn parse_records(
+                input: &str,
+            ) -> Result<Vec<Self>, $crate::parse::cursor::ParseError> {
+                $crate::parse::cursor::parse_all(input, |cursor| cursor.sections(Self::parse_record))
+            }
+        }
+    };
+
+    (@step $cursor:ident (text $text:literal)) => {
+        $cursor.expect_text($text)?;
+    };
+    (@step $cursor:ident (field $field:ident $type:ty)) => {
+        let $field: $type = $cursor.number()?;
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::parse::cursor::Cursor;
+
+    crate::parse_record! {
+        #[derive(Debug, PartialEq)]
+        struct Machine {
+            "Button A: X+", ax: i64, ", Y+", ay: i64, "\n",
+            "Prize: X=", px: i64, ", Y=", py: i64,
+        }
+    }
+
+    crate::parse_record! {
+        #[derive(Debug, PartialEq)]
+        struct Pair { a: u8, " -> ", b: u8 }
+    }
+
+    #[test]
+    fn test_parse_records() {
+        let input = "Button A: X+94, Y+34\r\nPrize: X=8400, Y=5400\r\n\r\n\r\n\
+                     Button A: X+1, Y+-2\r\nPrize: X=3, Y=4  \r\n\r\n\
+                     Button A: X+5, Y+6\r\nPrize: X=7, Y=8";
+        let machines = Machine::parse_records(input).unwrap();
+        assert_eq!(
+            machines,
+            [
+                Machine { ax: 94, ay: 34, px: 8400, py: 5400 },
+                Machine { ax: 1, ay: -2, px: 3, py: 4 },
+                Machine { ax: 5, ay: 6, px: 7, py: 8 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_record() {
+        let mut cursor = Cursor::new("3 -> 4, 5 -> 6");
+        assert_eq!(Pair::parse_record(&mut cursor), Ok(Pair { a: 3, b: 4 }));
+        assert_eq!(cursor.rest(), ", 5 -> 6");
+    }
+
+    #[test]
+    fn test_errors() {
+        let error = Machine::parse_records("Button A: X+94, Z+34").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 15));
+        assert_eq!(error.expected, "\", Y+\"");
+        assert_eq!(error.snippet, ", Z+34");
+
+        let input = "Button A: X+1, Y+2\nPrize: X=3, Y=4\n\nButton A: X+1, Y+2\nPrize: X=3, Y=4 extra\n";
+        let error = Machine::parse_records(input).unwrap_err();
+        assert_eq!((error.line, error.column), (5, 17));
+
+        let error = Pair::parse_records("1 -> 300").unwrap_err();
+        assert_eq!((error.line, error.column), (1, 6));
+        assert!(error.expected.starts_with("a number of type u8"));
+    }
+}
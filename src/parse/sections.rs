@@ -0,0 +1,179 @@
+use std::{error::Error, fmt::Display};
+
+/// An error from splitting input into sections or parsing one of them.
+#[derive(Debug)]
+pub enum SectionError {
+    /// The input did not have the expected number of sections.
+    Count { expected: usize, found: usize },
+    /// A section failed to parse. `index` counts from 1 and `line` is the
+    /// line of the input that the section starts on.
+    Invalid {
+        index: usize,
+        line: usize,
+        source: Box<dyn Error + Send + Sync>,
+    },
+}
+
+impl Error for SectionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SectionError::Count { .. } => None,
+            SectionError::Invalid { source, .. } => Some(source.as_ref()),
+        }
+    }
+}
+
+impl Display for SectionError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SectionError::Count { expected, found } => {
+                write!(f, "expected {} sections, found {}", expected, found)
+            }
+            SectionError::Invalid {
+                index,
+                line,
+                source,
+            } => write!(f, "section {} (from line {}): {}", index, line, source),
+        }
+    }
+}
+
+/// Splits `input` into `(first line, text)` pairs at runs of blank lines.
+fn split_sections(input: &str) -> Vec<(usize, &str)> {
+    let mut sections = Vec::new();
+    let mut start = None;
+    let mut end = 0;
+    let mut offset = 0;
+    for (i, line) in input.split_inclusive('\n').enumerate() {
+        if line.trim().is_empty() {
+            if let Some((first, from)) = start.take() {
+                sections.push((first, &input[from..end]));
+            }
+        } else {
+            start.get_or_insert((i + 1, offset));
+            end = offset + line.trim_end().len();
+        }
+        offset += line.len();
+    }
+    if let Some((first, from)) = start {
+        sections.push((first, &input[from..end]));
+    }
+    sections
+}
+
+/// The sections of `input`, which are separated by one or more lines that
+/// are empty or hold only whitespace. Blank lines at either end are
+/// ignored, and each section has its trailing whitespace removed. With
+/// `\r\n` line endings the lines within a section still end in `\r`, which
+/// [`str::lines`] strips but splitting on `'\n'` does not.
+pub fn sections(input: &str) -> impl Iterator<Item = &str> {
+    split_sections(input).into_iter().map(|(_, text)| text)
+}
+
+fn exactly<const N: usize>(input: &str) -> Result<[(usize, &str); N], SectionError> {
+    let found = split_sections(input);
+    let count = found.len();
+    found.try_into().map_err(|_| SectionError::Count {
+        expected: N,
+        found: count,
+    })
+}
+
+fn parse_section<T, E, F>(index: usize, (line, text): (usize, &str), parser: F) -> Result<T, SectionError>
+where
+    F: FnOnce(&str) -> Result<T, E>,
+    E: Into<Box<dyn Error + Send + Sync>>,
+{
+    parser(text).map_err(|e| SectionError::Invalid {
+        index,
+        line,
+        source: e.into(),
+    })
+}
+
+/// Parses an input of exactly two sections, each with its own parser.
+pub fn sections2<A, B, EA, EB>(
+    input: &str,
+    first: impl FnOnce(&str) -> Result<A, EA>,
+    second: impl FnOnce(&str) -> Result<B, EB>,
+) -> Result<(A, B), SectionError>
+where
+    EA: Into<Box<dyn Error + Send + Sync>>,
+    EB: Into<Box<dyn Error + Send + Sync>>,
+{
+    let [a, b] = exactly(input)?;
+    Ok((parse_section(1, a, first)?, parse_section(2, b, second)?))
+}
+
+/// Parses an input of exactly three sections, each with its own parser.
+pub fn sections3<A, B, C, EA, EB, EC>(
+    input: &str,
+    first: impl FnOnce(&str) -> Result<A, EA>,
+    second: impl FnOnce(&str) -> Result<B, EB>,
+    third: impl FnOnce(&str) -> Result<C, EC>,
+) -> Result<(A, B, C), SectionError>
+where
+    EA: Into<Box<dyn Error + Send + Sync>>,
+    EB: Into<Box<dyn Error + Send + Sync>>,
+    EC: Into<Box<dyn Error + Send + Sync>>,
+{
+    let [a, b, c] = exactly(input)?;
+    Ok((
+        parse_section(1, a, first)?,
+        parse_section(2, b, second)?,
+        parse_section(3, c, third)?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::num::ParseIntError;
+
+    use super::*;
+
+    fn numbers(text: &str) -> Result<Vec<u32>, ParseIntError> {
+        text.lines().map(str::parse).collect()
+    }
+
+    #[test]
+    fn test_split_sections() {
+        assert_eq!(split_sections("a\nb\n\nc\n"), [(1, "a\nb"), (4, "c")]);
+        assert_eq!(split_sections("a\n\n\n\nb"), [(1, "a"), (5, "b")]);
+        assert_eq!(split_sections("\n\na\n\n"), [(3, "a")]);
+        assert_eq!(split_sections("a\n  \t\nb  \n"), [(1, "a"), (3, "b")]);
+        assert_eq!(split_sections("a\r\nb\r\n\r\n\r\nc\r\n"), [(1, "a\r\nb"), (5, "c")]);
+        assert!(split_sections("").is_empty());
+        assert!(split_sections(" \n\r\n").is_empty());
+        assert_eq!(sections("x\n\ny\n").collect::<Vec<_>>(), ["x", "y"]);
+    }
+
+    #[test]
+    fn test_sections2() {
+        let input = "1\r\n2\r\n\r\n3\r\n";
+        let (a, b) = sections2(input, numbers, numbers).unwrap();
+        assert_eq!((a, b), (vec![1, 2], vec![3]));
+
+        let error = sections2("1\n\n2\n\n3", numbers, numbers).unwrap_err();
+        assert!(matches!(error, SectionError::Count { expected: 2, found: 3 }));
+        assert_eq!(error.to_string(), "expected 2 sections, found 3");
+
+        let error = sections2("1\n", numbers, numbers).unwrap_err();
+        assert!(matches!(error, SectionError::Count { expected: 2, found: 1 }));
+    }
+
+    #[test]
+    fn test_sections3() {
+        let input = "1\n\n\nx\ny\n\n3";
+        let text = |s: &str| Ok::<_, ParseIntError>(s.to_string());
+        let (a, b, c) = sections3(input, numbers, text, numbers).unwrap();
+        assert_eq!((a, b.as_str(), c), (vec![1], "x\ny", vec![3]));
+
+        let error = sections3("1\n\n2\n\n\nx", numbers, numbers, numbers).unwrap_err();
+        assert!(matches!(error, SectionError::Invalid { index: 3, line: 6, .. }));
+        assert_eq!(
+            error.to_string(),
+            "section 3 (from line 6): invalid digit found in string"
+        );
+        assert!(error.source().is_some());
+    }
+}
@@ -0,0 +1,429 @@
+use core::fmt;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+use crate::parse::Parseable;
+
+use super::ipoint::IPoint;
+
+/// A hexagon on a pointy-topped hex grid in axial coordinates. Moving east
+/// increases `q`, moving south-east increases `r`, and the implied third
+/// cube coordinate is `s = -q - r`.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug, PartialOrd, Ord, Hash)]
+pub struct Hex {
+    pub q: isize,
+    pub r: isize,
+}
+
+impl fmt::Display for Hex {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({},{})", self.q, self.r)
+    }
+}
+
+impl Hex {
+    pub fn new(q: isize, r: isize) -> Self {
+        Hex { q, r }
+    }
+
+    /// Builds a hex from cube coordinates, which must sum to zero.
+    pub fn from_cube(q: isize, r: isize, s: isize) -> Self {
+        debug_assert_eq!(q + r + s, 0, "cube coordinates must sum to zero");
+        Hex { q, r }
+    }
+
+    pub fn s(&self) -> isize {
+        -self.q - self.r
+    }
+
+    pub fn cube(&self) -> (isize, isize, isize) {
+        (self.q, self.r, self.s())
+    }
+
+    pub fn step(&self, dir: HexDirection) -> Self {
+        *self + dir.offset()
+    }
+
+    /// The six surrounding hexes, in [`HexDirection::ALL`] order.
+    pub fn neighbors(&self) -> [Hex; 6] {
+        HexDirection::ALL.map(|dir| self.step(dir))
+    }
+
+    /// The number of steps between two hexes.
+    pub fn distance(&self, to: Hex) -> isize {
+        let off = to - *self;
+        (off.q.abs() + off.r.abs() + off.s().abs()) / 2
+    }
+
+    /// The position in "odd-r" offset coordinates, where odd rows are
+    /// shifted half a hex to the right.
+    pub fn to_offset(&self) -> IPoint {
+        IPoint::new(self.q + (self.r - (self.r & 1)) / 2, self.r)
+    }
+
+    /// The hex at a position in "odd-r" offset coordinates.
+    pub fn from_offset(point: IPoint) -> Self {
+        Hex::new(point.x - (point.y - (point.y & 1)) / 2, point.y)
+    }
+}
+
+impl Add for Hex {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Hex::new(self.q + rhs.q, self.r + rhs.r)
+    }
+}
+
+impl Sub for Hex {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Hex::new(self.q - rhs.q, self.r - rhs.r)
+    }
+}
+
+impl Mul<isize> for Hex {
+    type Output = Self;
+
+    fn mul(self, rhs: isize) -> Self::Output {
+        Hex::new(self.q * rhs, self.r * rhs)
+    }
+}
+
+impl Neg for Hex {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Hex::new(-self.q, -self.r)
+    }
+}
+
+impl AddAssign for Hex {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for Hex {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl std::iter::Sum for Hex {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Hex::default(), |acc, item| acc + item)
+    }
+}
+
+/// The six directions on a pointy-topped hex grid, ordered clockwise from east.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HexDirection {
+    East,
+    SouthEast,
+    SouthWest,
+    West,
+    NorthWest,
+    NorthEast,
+}
+
+impl HexDirection {
+    pub const ALL: [HexDirection; 6] = [
+        HexDirection::East,
+        HexDirection::SouthEast,
+        HexDirection::SouthWest,
+        HexDirection::West,
+        HexDirection::NorthWest,
+        HexDirection::NorthEast,
+    ];
+
+    /// Turns 60 degrees clockwise.
+    pub fn rotate_clockwise(&self) -> Self {
+        Self::ALL[(*self as usize + 1) % 6]
+    }
+
+    /// Turns 60 degrees counterclockwise.
+    pub fn rotate_counterclockwise(&self) -> Self {
+        Self::ALL[(*self as usize + 5) % 6]
+    }
+
+    pub fn reverse(&self) -> Self {
+        Self::ALL[(*self as usize + 3) % 6]
+    }
+
+    pub fn offset(&self) -> Hex {
+        match self {
+            HexDirection::East => Hex::new(1, 0),
+            HexDirection::SouthEast => Hex::new(0, 1),
+            HexDirection::SouthWest => Hex::new(-1, 1),
+            HexDirection::West => Hex::new(-1, 0),
+            HexDirection::NorthWest => Hex::new(0, -1),
+            HexDirection::NorthEast => Hex::new(1, -1),
+        }
+    }
+
+    /// The direction written as `e`, `se`, `sw`, `w`, `nw` or `ne`.
+    pub fn token(&self) -> &'static str {
+        match self {
+            HexDirection::East => "e",
+            HexDirection::SouthEast => "se",
+            HexDirection::SouthWest => "sw",
+            HexDirection::West => "w",
+            HexDirection::NorthWest => "nw",
+            HexDirection::NorthEast => "ne",
+        }
+    }
+}
+
+impl fmt::Display for HexDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.token())
+    }
+}
+
+impl From<HexDirection> for Hex {
+    fn from(value: HexDirection) -> Self {
+        value.offset()
+    }
+}
+
+/// Reads the next direction token from an unseparated run such as `nwwswee`,
+/// skipping any bytes between tokens that cannot start one, including an `n`
+/// or `s` that is not followed by `e` or `w`. Returns `None` at the end of
+/// input. Flat-topped tokens such as `n` are read by the
+/// [`FlatHexDirection`] parser instead.
+impl<I> Parseable<HexDirection> for I
+where
+    I: Iterator<Item = u8>,
+{
+    fn next_number(&mut self) -> Option<HexDirection> {
+        let mut byte = self.next()?;
+        loop {
+            byte = match byte {
+                b'e' => return Some(HexDirection::East),
+                b'w' => return Some(HexDirection::West),
+                b'n' | b's' => match (byte, self.next()?) {
+                    (b'n', b'e') => return Some(HexDirection::NorthEast),
+                    (b'n', b'w') => return Some(HexDirection::NorthWest),
+                    (b's', b'e') => return Some(HexDirection::SouthEast),
+                    (b's', b'w') => return Some(HexDirection::SouthWest),
+                    // The byte after a lone `n` or `s` may start a token itself
+                    (_, next) => next,
+                },
+                _ => self.next()?,
+            };
+        }
+    }
+}
+
+/// The six directions on a flat-topped hex grid, ordered clockwise from
+/// north. They step through the same axial coordinates as [`HexDirection`],
+/// turned by 30 degrees, so [`Hex::distance`] applies unchanged but the
+/// "odd-r" offset conversions do not match a flat-topped layout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FlatHexDirection {
+    North,
+    NorthEast,
+    SouthEast,
+    South,
+    SouthWest,
+    NorthWest,
+}
+
+impl FlatHexDirection {
+    pub const ALL: [FlatHexDirection; 6] = [
+        FlatHexDirection::North,
+        FlatHexDirection::NorthEast,
+        FlatHexDirection::SouthEast,
+        FlatHexDirection::South,
+        FlatHexDirection::SouthWest,
+        FlatHexDirection::NorthWest,
+    ];
+
+    /// Turns 60 degrees clockwise.
+    pub fn rotate_clockwise(&self) -> Self {
+        Self::ALL[(*self as usize + 1) % 6]
+    }
+
+    /// Turns 60 degrees counterclockwise.
+    pub fn rotate_counterclockwise(&self) -> Self {
+        Self::ALL[(*self as usize + 5) % 6]
+    }
+
+    pub fn reverse(&self) -> Self {
+        Self::ALL[(*self as usize + 3) % 6]
+    }
+
+    pub fn offset(&self) -> Hex {
+        match self {
+            FlatHexDirection::North => Hex::new(0, -1),
+            FlatHexDirection::NorthEast => Hex::new(1, -1),
+            FlatHexDirection::SouthEast => Hex::new(1, 0),
+            FlatHexDirection::South => Hex::new(0, 1),
+            FlatHexDirection::SouthWest => Hex::new(-1, 1),
+            FlatHexDirection::NorthWest => Hex::new(-1, 0),
+        }
+    }
+
+    /// The direction written as `n`, `ne`, `se`, `s`, `sw` or `nw`.
+    pub fn token(&self) -> &'static str {
+        match self {
+            FlatHexDirection::North => "n",
+            FlatHexDirection::NorthEast => "ne",
+            FlatHexDirection::SouthEast => "se",
+            FlatHexDirection::South => "s",
+            FlatHexDirection::SouthWest => "sw",
+            FlatHexDirection::NorthWest => "nw",
+        }
+    }
+}
+
+impl fmt::Display for FlatHexDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.token())
+    }
+}
+
+impl From<FlatHexDirection> for Hex {
+    fn from(value: FlatHexDirection) -> Self {
+        value.offset()
+    }
+}
+
+/// Reads the next direction token from a list such as `ne,ne,s,s`, skipping
+/// any bytes between tokens that cannot start one. Since `n` followed by `e`
+/// always reads as `ne`, tokens must be separated; the byte after an `n` or
+/// `s` that ends a token is consumed.
+impl<I> Parseable<FlatHexDirection> for I
+where
+    I: Iterator<Item = u8>,
+{
+    fn next_number(&mut self) -> Option<FlatHexDirection> {
+        loop {
+            let (straight, east, west) = match self.next()? {
+                b'n' => (
+                    FlatHexDirection::North,
+                    FlatHexDirection::NorthEast,
+                    FlatHexDirection::NorthWest,
+                ),
+                b's' => (
+                    FlatHexDirection::South,
+                    FlatHexDirection::SouthEast,
+                    FlatHexDirection::SouthWest,
+                ),
+                _ => continue,
+            };
+            return Some(match self.next() {
+                Some(b'e') => east,
+                Some(b'w') => west,
+                _ => straight,
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::iter::from_fn;
+
+    use super::*;
+
+    fn directions(input: &str) -> Vec<HexDirection> {
+        let mut bytes = input.bytes();
+        from_fn(|| bytes.next_number()).collect()
+    }
+
+    fn flat_directions(input: &str) -> Vec<FlatHexDirection> {
+        let mut bytes = input.bytes();
+        from_fn(|| bytes.next_number()).collect()
+    }
+
+    #[test]
+    fn test_offset() {
+        for y in -5..=5 {
+            for x in -5..=5 {
+                let point = IPoint::new(x, y);
+                assert_eq!(Hex::from_offset(point).to_offset(), point);
+            }
+        }
+
+        // Odd rows, including negative ones, sit half a hex to the right
+        for y in [-3, -1, 1, 3] {
+            let hex = Hex::from_offset(IPoint::new(2, y));
+            let ne = IPoint::new(3, y - 1);
+            let nw = IPoint::new(2, y - 1);
+            assert_eq!(hex.step(HexDirection::NorthEast).to_offset(), ne);
+            assert_eq!(hex.step(HexDirection::NorthWest).to_offset(), nw);
+        }
+        for y in [-2, 0, 2] {
+            let hex = Hex::from_offset(IPoint::new(2, y));
+            assert_eq!(hex.step(HexDirection::SouthEast).to_offset(), IPoint::new(2, y + 1));
+            assert_eq!(hex.step(HexDirection::SouthWest).to_offset(), IPoint::new(1, y + 1));
+        }
+    }
+
+    #[test]
+    fn test_distance() {
+        let origin = Hex::default();
+        assert!(origin.neighbors().iter().all(|n| origin.distance(*n) == 1));
+        assert_eq!(origin.distance(Hex::new(3, -1)), 3);
+        assert_eq!(origin.distance(Hex::new(-2, -2)), 4);
+        assert_eq!(Hex::new(3, -1).distance(Hex::new(-2, -2)), 6);
+        assert_eq!(Hex::from_cube(1, 2, -3).cube(), (1, 2, -3));
+
+        let far = HexDirection::ALL.iter().map(|d| d.offset() * 2).sum::<Hex>();
+        assert_eq!(far, origin);
+    }
+
+    #[test]
+    fn test_rotation() {
+        for dir in HexDirection::ALL {
+            let (q, r, s) = dir.offset().cube();
+            // A 60 degree turn clockwise maps cube (q, r, s) to (-r, -s, -q)
+            assert_eq!(dir.rotate_clockwise().offset().cube(), (-r, -s, -q));
+            assert_eq!(dir.rotate_clockwise().rotate_counterclockwise(), dir);
+            assert_eq!(dir.reverse().offset(), -dir.offset());
+        }
+        assert_eq!(HexDirection::NorthEast.rotate_clockwise(), HexDirection::East);
+        assert_eq!(HexDirection::East.rotate_counterclockwise(), HexDirection::NorthEast);
+    }
+
+    #[test]
+    fn test_parse() {
+        use HexDirection::*;
+
+        let path = directions("nwwswee");
+        assert_eq!(path, [NorthWest, West, SouthWest, East, East]);
+        assert_eq!(path.iter().map(|d| d.offset()).sum::<Hex>(), Hex::default());
+        assert_eq!(directions("esenee\n"), [East, SouthEast, NorthEast, East]);
+
+        let tokens: String = HexDirection::ALL.iter().map(|d| d.token()).collect();
+        assert_eq!(directions(&tokens), HexDirection::ALL);
+
+        assert_eq!(directions("nx"), []);
+        assert_eq!(directions("ex"), [East]);
+        assert_eq!(directions("s"), []);
+        assert_eq!(directions("ne,n,se"), [NorthEast, SouthEast]);
+        assert_eq!(directions("nsw"), [SouthWest]);
+    }
+
+    #[test]
+    fn test_flat_directions() {
+        use FlatHexDirection::*;
+
+        for dir in FlatHexDirection::ALL {
+            let (q, r, s) = dir.offset().cube();
+            assert_eq!(dir.rotate_clockwise().offset().cube(), (-r, -s, -q));
+            assert_eq!(dir.rotate_clockwise().rotate_counterclockwise(), dir);
+            assert_eq!(dir.reverse().offset(), -dir.offset());
+        }
+
+        let tokens: Vec<&str> = FlatHexDirection::ALL.iter().map(|d| d.token()).collect();
+        assert_eq!(flat_directions(&tokens.join(",")), FlatHexDirection::ALL);
+        assert_eq!(flat_directions("ne,ne,s,s\n"), [NorthEast, NorthEast, South, South]);
+        assert_eq!(flat_directions("x,n"), [North]);
+
+        let path = flat_directions("se,sw,se,sw,sw");
+        assert_eq!(path.iter().map(|d| d.offset()).sum::<Hex>().distance(Hex::default()), 3);
+    }
+}
@@ -1,308 +1,172 @@
-use std::{ops::{Mul, Sub, Add, DivAssign, Div, MulAssign, SubAssign, AddAssign}, iter::Sum};
-use forward_ref::{forward_ref_binop, forward_ref_op_assign};
+use super::{Point, Point2};
 
-use crate::parse::Parseable;
-
-use super::{Point, traits::{ModuloPositiveAssign, ModuloPositive, ModuloAssign, Modulo, Absolute}};
-
-#[derive(Default,Copy,Clone,Debug,PartialEq,Eq,Hash)]
-pub struct IPoint {
-	pub x : isize,
-	pub y : isize,
-}
+/// A point on an unbounded plane, where components may be negative.
+pub type IPoint = Point2<isize>;
 
 impl IPoint {
-	pub fn new( x : isize, y : isize ) -> Self {
-		Self {
-			x,
-			y,
-		}
-	}
-
+    /// Converts an unsigned point.
+    ///
+    /// # Panics
+    ///
+    /// If either component is too large for an `isize`.
     pub fn from_point(point: Point) -> Self {
-        Self {
-            x: point.x as isize,
-            y: point.y as isize,
-        }
-    }
-
-	pub fn manhatten_dist( &self, to : IPoint ) -> isize {
-		(to.x - self.x).abs() + (to.y - self.y).abs()
-	}
-
-	pub fn dir_to( &self, to : IPoint ) -> IPoint {
-		let off = to - *self;
-		IPoint {
-			x: off.x.signum(),
-			y: off.y.signum(),
-		}
-	}
-
-    pub fn in_bounds(&self, width: usize, height: usize) -> bool {
-        self.x >= 0 && self.x < width as isize && self.y >= 0 && self.y < height as isize
-    }
-}
-
-impl<T: Iterator<Item = u8>> Parseable<IPoint> for T {
-    fn next_number(&mut self) -> Option<IPoint> {
-        if let Some((x, y)) = self.next_number().zip(self.next_number()) {
-            return Some(IPoint::new(x, y));
-        }
-        None
-    }
-}
-impl Add for IPoint {
-    type Output = IPoint;
-
-    fn add(self, rhs: Self) -> Self::Output {
-        IPoint {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-        }
-    }
-}
-
-impl AddAssign<IPoint> for IPoint {
-    fn add_assign(&mut self, rhs: IPoint) {
-        self.x += rhs.x;
-        self.y += rhs.y;
-    }
-}
-forward_ref_binop!(impl Add, add for IPoint, IPoint);
-forward_ref_op_assign!(impl AddAssign, add_assign for IPoint, IPoint);
-
-impl Sub for IPoint {
-    type Output = IPoint;
-
-    fn sub(self, rhs: Self) -> Self::Output {
-        IPoint {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
-        }
-    }
-}
-
-impl SubAssign<IPoint> for IPoint {
-    fn sub_assign(&mut self, rhs: IPoint) {
-        self.x -= rhs.x;
-        self.y -= rhs.y;
-    }
-}
-
-forward_ref_binop!(impl Sub, sub for IPoint, IPoint);
-forward_ref_op_assign!(impl SubAssign, sub_assign for IPoint, IPoint);
-
-impl Mul<isize> for IPoint {
-    type Output = IPoint;
-
-    fn mul(self, rhs: isize) -> Self::Output {
-        IPoint {
-            x: self.x * rhs,
-            y: self.y * rhs,
-        }
+        Self::try_from(point).unwrap_or_else(|e| panic!("{}", e))
     }
-}
 
-impl MulAssign<isize> for IPoint {
-    fn mul_assign(&mut self, rhs: isize) {
-        self.x *= rhs;
-        self.y *= rhs;
+    pub fn manhatten_dist(&self, to: IPoint) -> isize {
+        (to.x - self.x).abs() + (to.y - self.y).abs()
     }
-}
-
-forward_ref_binop!(impl Mul, mul for IPoint, isize);
-forward_ref_op_assign!(impl MulAssign, mul_assign for IPoint, isize);
-
-impl Div<isize> for IPoint {
-    type Output = IPoint;
 
-    fn div(self, rhs: isize) -> Self::Output {
-        IPoint {
-            x: self.x / rhs,
-            y: self.y / rhs,
-        }
+    pub fn chebyshev_dist(&self, to: IPoint) -> isize {
+        (to.x - self.x).abs().max((to.y - self.y).abs())
     }
-}
 
-impl DivAssign<isize> for IPoint {
-    fn div_assign(&mut self, rhs: isize) {
-        self.x /= rhs;
-        self.y /= rhs;
+    /// The squared straight-line distance, which stays exact in integers.
+    pub fn euclidean_dist_squared(&self, to: IPoint) -> isize {
+        let off = to - *self;
+        off.x * off.x + off.y * off.y
     }
-}
 
-forward_ref_binop!(impl Div, div for IPoint, isize);
-forward_ref_op_assign!(impl DivAssign, div_assign for IPoint, isize);
-
-impl Modulo<isize> for IPoint {
-    type Output = IPoint;
-
-    fn modulo(self, rhs: isize) -> Self::Output {
+    pub fn dir_to(&self, to: IPoint) -> IPoint {
+        let off = to - *self;
         IPoint {
-            x: self.x % rhs,
-            y: self.y % rhs,
+            x: off.x.signum(),
+            y: off.y.signum(),
         }
     }
-}
-
-impl ModuloAssign<isize> for IPoint {
-    fn modulo_assign(&mut self, rhs: isize) {
-        self.x %= rhs;
-        self.y %= rhs;
-    }
-}
-
-forward_ref_binop!(impl Modulo, modulo for IPoint, isize);
-forward_ref_op_assign!(impl ModuloAssign, modulo_assign for IPoint, isize);
-
-impl Mul<IPoint> for IPoint {
-    type Output = IPoint;
 
-    fn mul(self, rhs: IPoint) -> Self::Output {
-        IPoint {
-            x: self.x * rhs.x,
-            y: self.y * rhs.y,
-        }
-    }
-}
-
-impl MulAssign<IPoint> for IPoint {
-    fn mul_assign(&mut self, rhs: IPoint) {
-        self.x *= rhs.x;
-        self.y *= rhs.y;
-    }
-}
-
-forward_ref_binop!(impl Mul, mul for IPoint, IPoint);
-forward_ref_op_assign!(impl MulAssign, mul_assign for IPoint, IPoint);
-
-impl Div<IPoint> for IPoint {
-    type Output = IPoint;
-
-    fn div(self, rhs: IPoint) -> Self::Output {
-        IPoint {
-            x: self.x / rhs.x,
-            y: self.y / rhs.y,
-        }
-    }
-}
-
-impl DivAssign<IPoint> for IPoint {
-    fn div_assign(&mut self, rhs: IPoint) {
-        self.x /= rhs.x;
-        self.y /= rhs.y;
-    }
-}
-
-forward_ref_binop!(impl Div, div for IPoint, IPoint);
-forward_ref_op_assign!(impl DivAssign, div_assign for IPoint, IPoint);
-
-impl Modulo<IPoint> for IPoint {
-    type Output = IPoint;
-
-    fn modulo(self, rhs: IPoint) -> Self::Output {
-        IPoint {
-            x: self.x % rhs.x,
-            y: self.y % rhs.y,
-        }
-    }
-}
-
-impl ModuloAssign<IPoint> for IPoint {
-    fn modulo_assign(&mut self, rhs: IPoint) {
-        self.x %= rhs.x;
-        self.y %= rhs.y;
-    }
-}
-
-forward_ref_binop!(impl Modulo, modulo for IPoint, IPoint);
-forward_ref_op_assign!(impl ModuloAssign, modulo_assign for IPoint, IPoint);
-
-impl ModuloPositive<IPoint> for IPoint {
-    type Output = IPoint;
-
-    fn modulo_positive(self, rhs: IPoint) -> Self::Output {
-        let mut x = self.x % rhs.x;
-        let mut y = self.y % rhs.y;
-
-        if x < 0 {
-            x += rhs.x;
-        }
-
-        if y < 0 {
-            y += rhs.y;
-        }
-
-        IPoint { x, y }
-    }
-}
-
-impl ModuloPositiveAssign<IPoint> for IPoint {
-    fn module_positive_assign(&mut self, rhs: IPoint) {
-        self.x %= rhs.x;
-        self.y %= rhs.y;
-
-        if self.x < 0 {
-            self.x += rhs.x;
-        }
-
-        if self.y < 0 {
-            self.y += rhs.y;
-        }
-    }
-}
-
-forward_ref_binop!(impl ModuloPositive, modulo_positive for IPoint, IPoint);
-forward_ref_op_assign!(impl ModuloPositiveAssign, module_positive_assign for IPoint, IPoint);
-
-impl ModuloPositive<isize> for IPoint {
-    type Output = IPoint;
-
-    fn modulo_positive(self, rhs: isize) -> Self::Output {
-        let mut x = self.x % rhs;
-        let mut y = self.y % rhs;
-
-        if x < 0 {
-            x += rhs;
-        }
-
-        if y < 0 {
-            y += rhs;
-        }
-
-        IPoint { x, y }
-    }
-}
-
-impl ModuloPositiveAssign<isize> for IPoint {
-    fn module_positive_assign(&mut self, rhs: isize) {
-        self.x %= rhs;
-        self.y %= rhs;
-
-        if self.x < 0 {
-            self.x += rhs;
-        }
-
-        if self.y < 0 {
-            self.y += rhs;
-        }
-    }
-}
-
-forward_ref_binop!(impl ModuloPositive, modulo_positive for IPoint, isize);
-forward_ref_op_assign!(impl ModuloPositiveAssign, module_positive_assign for IPoint, isize);
-
-impl Absolute for IPoint {
-    fn absolute(self) -> Self {
-        IPoint {
-            x: self.x.abs(),
-            y: self.y.abs(),
-        }
+    pub fn in_bounds(&self, width: usize, height: usize) -> bool {
+        self.x >= 0 && self.x < width as isize && self.y >= 0 && self.y < height as isize
     }
-}
 
-impl Sum for IPoint {
-    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
-        iter.fold(IPoint::new(0, 0), |acc, item| acc + item)
+    /// The equivalent unsigned point, if this lies within a `width` by `height` grid.
+    pub fn to_point_in(&self, width: usize, height: usize) -> Option<Point> {
+        Point::try_from(*self)
+            .ok()
+            .filter(|p| p.x < width && p.y < height)
+    }
+
+    /// Every point within Manhattan distance `radius`, including this one,
+    /// row by row from the top of the diamond.
+    pub fn within_manhattan(self, radius: isize) -> impl Iterator<Item = IPoint> {
+        (-radius..=radius).flat_map(move |dy| {
+            let width = radius - dy.abs();
+            (-width..=width).map(move |dx| self + IPoint::new(dx, dy))
+        })
+    }
+
+    /// Every point within Chebyshev distance `radius`, including this one,
+    /// row by row from the top of the square.
+    pub fn within_chebyshev(self, radius: isize) -> impl Iterator<Item = IPoint> {
+        (-radius..=radius)
+            .flat_map(move |dy| (-radius..=radius).map(move |dx| self + IPoint::new(dx, dy)))
+    }
+
+    /// The points at exactly Manhattan distance `distance`, clockwise from
+    /// the easternmost.
+    pub fn manhattan_ring(self, distance: isize) -> impl Iterator<Item = IPoint> {
+        let d = distance;
+        let sides = [
+            (IPoint::new(d, 0), IPoint::new(-1, 1)),
+            (IPoint::new(0, d), IPoint::new(-1, -1)),
+            (IPoint::new(-d, 0), IPoint::new(1, -1)),
+            (IPoint::new(0, -d), IPoint::new(1, 1)),
+        ];
+        self.ring(sides, d)
+    }
+
+    /// The points at exactly Chebyshev distance `distance`, clockwise from
+    /// the north-west corner.
+    pub fn chebyshev_ring(self, distance: isize) -> impl Iterator<Item = IPoint> {
+        let d = distance;
+        let sides = [
+            (IPoint::new(-d, -d), IPoint::new(1, 0)),
+            (IPoint::new(d, -d), IPoint::new(0, 1)),
+            (IPoint::new(d, d), IPoint::new(-1, 0)),
+            (IPoint::new(-d, d), IPoint::new(0, -1)),
+        ];
+        self.ring(sides, 2 * d)
+    }
+
+    /// Walks four sides given as `(start, step)` offsets, `len` steps each.
+    /// A ring of distance zero is just the centre.
+    fn ring(self, sides: [(IPoint, IPoint); 4], len: isize) -> impl Iterator<Item = IPoint> {
+        let centre = (len == 0).then_some(self);
+        centre.into_iter().chain(
+            sides
+                .into_iter()
+                .flat_map(move |(start, step)| (0..len).map(move |k| self + start + step * k)),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    const CENTRE: IPoint = IPoint { x: 3, y: -2 };
+
+    #[test]
+    fn test_distances() {
+        let to = IPoint::new(-1, 1);
+        assert_eq!(CENTRE.manhatten_dist(to), 7);
+        assert_eq!(CENTRE.chebyshev_dist(to), 4);
+        assert_eq!(CENTRE.euclidean_dist_squared(to), 25);
+        assert_eq!(CENTRE.dir_to(to), IPoint::new(-1, 1));
+    }
+
+    #[test]
+    fn test_within() {
+        for r in 0..5 {
+            let diamond: Vec<_> = CENTRE.within_manhattan(r).collect();
+            assert_eq!(diamond.len() as isize, 2 * r * r + 2 * r + 1);
+            assert!(diamond.iter().all(|p| CENTRE.manhatten_dist(*p) <= r));
+            assert_eq!(diamond.iter().collect::<HashSet<_>>().len(), diamond.len());
+
+            let square: Vec<_> = CENTRE.within_chebyshev(r).collect();
+            assert_eq!(square.len() as isize, (2 * r + 1) * (2 * r + 1));
+            assert!(square.iter().all(|p| CENTRE.chebyshev_dist(*p) <= r));
+            assert_eq!(square.iter().collect::<HashSet<_>>().len(), square.len());
+        }
+        assert_eq!(
+            IPoint::new(0, 0).within_manhattan(1).collect::<Vec<_>>(),
+            [
+                IPoint::new(0, -1),
+                IPoint::new(-1, 0),
+                IPoint::new(0, 0),
+                IPoint::new(1, 0),
+                IPoint::new(0, 1)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_rings() {
+        assert_eq!(CENTRE.manhattan_ring(0).collect::<Vec<_>>(), [CENTRE]);
+        assert_eq!(CENTRE.chebyshev_ring(0).collect::<Vec<_>>(), [CENTRE]);
+
+        for d in 1..5 {
+            let ring: Vec<_> = CENTRE.manhattan_ring(d).collect();
+            assert_eq!(ring.len() as isize, 4 * d);
+            assert!(ring.iter().all(|p| CENTRE.manhatten_dist(*p) == d));
+            assert_eq!(ring.iter().collect::<HashSet<_>>().len(), ring.len());
+            assert_eq!(ring[0], CENTRE + IPoint::new(d, 0));
+
+            let ring: Vec<_> = CENTRE.chebyshev_ring(d).collect();
+            assert_eq!(ring.len() as isize, 8 * d);
+            assert!(ring.iter().all(|p| CENTRE.chebyshev_dist(*p) == d));
+            assert_eq!(ring.iter().collect::<HashSet<_>>().len(), ring.len());
+            assert_eq!(ring[0], CENTRE + IPoint::new(-d, -d));
+        }
+    }
+
+    #[test]
+    fn test_to_point_in() {
+        assert_eq!(IPoint::new(2, 1).to_point_in(3, 2), Some(Point::new(2, 1)));
+        assert_eq!(IPoint::new(3, 1).to_point_in(3, 2), None);
+        assert_eq!(IPoint::new(0, -1).to_point_in(3, 2), None);
+        assert!(!IPoint::new(-1, 0).in_bounds(3, 2));
     }
 }
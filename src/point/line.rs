@@ -0,0 +1,176 @@
+use crate::{math::gcd, rect::Rect};
+
+use super::ipoint::IPoint;
+
+/// The smallest step from `from` towards `to` that still lands on every
+/// lattice point between them, e.g. `(4,6)` reduces to `(2,3)`.
+pub fn lattice_step(from: IPoint, to: IPoint) -> IPoint {
+    let off = to - from;
+    match gcd(off.x, off.y) {
+        0 => off,
+        divisor => off / divisor,
+    }
+}
+
+/// Points reached by repeatedly adding `step`, stopping at the edge of
+/// `bounds`.
+pub struct Walk {
+    next: Option<IPoint>,
+    step: IPoint,
+    bounds: Rect<isize>,
+}
+
+impl Walk {
+    pub fn new(start: IPoint, step: IPoint, bounds: Rect<isize>) -> Self {
+        let next = bounds.contains(start).then_some(start);
+        Walk { next, step, bounds }
+    }
+}
+
+impl Iterator for Walk {
+    type Item = IPoint;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        let next = current + self.step;
+        self.next = (self.step != IPoint::default() && self.bounds.contains(next)).then_some(next);
+        Some(current)
+    }
+}
+
+/// Every lattice point on the infinite line through `a` and `b` that lies
+/// within `bounds`, in order from one edge to the other. If `a` and `b` are
+/// the same point, only that point is returned.
+pub fn line_through(a: IPoint, b: IPoint, bounds: Rect<isize>) -> Walk {
+    let step = lattice_step(a, b);
+    let mut start = a;
+    if step != IPoint::default() {
+        while bounds.contains(start - step) {
+            start -= step;
+        }
+    }
+    Walk::new(start, step, bounds)
+}
+
+/// The cells of a straight line from `from` to `to`, both included, as drawn
+/// by Bresenham's algorithm.
+pub struct Bresenham {
+    current: IPoint,
+    to: IPoint,
+    dx: isize,
+    dy: isize,
+    sx: isize,
+    sy: isize,
+    error: isize,
+    done: bool,
+}
+
+impl Bresenham {
+    pub fn new(from: IPoint, to: IPoint) -> Self {
+        let dx = (to.x - from.x).abs();
+        let dy = -(to.y - from.y).abs();
+        Bresenham {
+            current: from,
+            to,
+            dx,
+            dy,
+            sx: (to.x - from.x).signum(),
+            sy: (to.y - from.y).signum(),
+            error: dx + dy,
+            done: false,
+        }
+    }
+}
+
+impl Iterator for Bresenham {
+    type Item = IPoint;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let point = self.current;
+        if point == self.to {
+            self.done = true;
+            return Some(point);
+        }
+
+        let doubled = 2 * self.error;
+        if doubled >= self.dy {
+            self.error += self.dy;
+            self.current.x += self.sx;
+        }
+        if doubled <= self.dx {
+            self.error += self.dx;
+            self.current.y += self.sy;
+        }
+        Some(point)
+    }
+}
+
+pub fn bresenham(from: IPoint, to: IPoint) -> Bresenham {
+    Bresenham::new(from, to)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(coords: &[(isize, isize)]) -> Vec<IPoint> {
+        coords.iter().map(|&(x, y)| IPoint::new(x, y)).collect()
+    }
+
+    #[test]
+    fn test_lattice_step() {
+        assert_eq!(lattice_step(IPoint::new(1, 1), IPoint::new(5, 7)), IPoint::new(2, 3));
+        assert_eq!(lattice_step(IPoint::new(0, 0), IPoint::new(0, -6)), IPoint::new(0, -1));
+        assert_eq!(lattice_step(IPoint::new(2, 2), IPoint::new(2, 2)), IPoint::new(0, 0));
+    }
+
+    #[test]
+    fn test_line_through() {
+        let bounds = Rect::from_size(5, 5);
+        let line = |a: (isize, isize), b: (isize, isize)| {
+            line_through(IPoint::new(a.0, a.1), IPoint::new(b.0, b.1), bounds).collect::<Vec<_>>()
+        };
+
+        assert_eq!(line((2, 3), (2, 3)), points(&[(2, 3)]));
+        assert_eq!(line((1, 2), (1, 3)), points(&[(1, 0), (1, 1), (1, 2), (1, 3), (1, 4)]));
+        assert_eq!(line((1, 0), (2, 3)), points(&[(1, 0), (2, 3)]));
+        assert_eq!(line((0, 4), (4, 0)), line((2, 2), (3, 1)));
+        assert_eq!(line((0, 4), (4, 0)).len(), 5);
+        assert_eq!(line((0, 0), (4, 2)), points(&[(0, 0), (2, 1), (4, 2)]));
+        assert!(line((5, 0), (5, 0)).is_empty());
+    }
+
+    #[test]
+    fn test_walk() {
+        let bounds = Rect::from_size(10, 3);
+        let walk = Walk::new(IPoint::new(1, 0), IPoint::new(3, 1), bounds);
+        assert_eq!(walk.collect::<Vec<_>>(), points(&[(1, 0), (4, 1), (7, 2)]));
+        assert_eq!(Walk::new(IPoint::new(0, 0), IPoint::new(0, 0), bounds).count(), 1);
+        assert_eq!(Walk::new(IPoint::new(-1, 0), IPoint::new(1, 0), bounds).count(), 0);
+    }
+
+    #[test]
+    fn test_bresenham_octants() {
+        let origin = IPoint::new(0, 0);
+        for end in origin.chebyshev_ring(3).chain(origin.chebyshev_ring(5)) {
+            let line: Vec<_> = bresenham(origin, end).collect();
+            let (dx, dy) = (end.x.abs(), end.y.abs());
+            assert_eq!(line.len() as isize, dx.max(dy) + 1, "to {}", end);
+            assert_eq!(line[0], origin);
+            assert_eq!(*line.last().unwrap(), end);
+            for step in line.windows(2) {
+                assert_eq!(step[0].chebyshev_dist(step[1]), 1, "to {}", end);
+            }
+            assert_eq!(bresenham(end, origin).count(), line.len());
+        }
+
+        assert_eq!(
+            bresenham(IPoint::new(0, 0), IPoint::new(5, -2)).collect::<Vec<_>>(),
+            points(&[(0, 0), (1, 0), (2, -1), (3, -1), (4, -2), (5, -2)])
+        );
+        assert_eq!(bresenham(IPoint::new(1, 1), IPoint::new(1, 1)).count(), 1);
+    }
+}
@@ -1,149 +1,343 @@
 use core::fmt;
-use std::ops::{Add, Sub};
+use std::{
+    error::Error,
+    iter::Sum,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use forward_ref::{forward_ref_binop, forward_ref_op_assign, forward_ref_unop};
 
 use crate::parse::Parseable;
 
-pub mod ipoint;
-pub mod traits;
+use traits::{
+    Absolute, Integer, Modulo, ModuloAssign, ModuloPositive, ModuloPositiveAssign,
+};
 
-#[derive(Clone, Copy, PartialEq, Eq, Debug, PartialOrd, Ord, Hash)]
-pub struct Point {
-    pub x: usize,
-    pub y: usize,
-}
+/// Implements a component-wise binary operator and its assigning form for a
+/// point type, both between two points and between a point and a scalar.
+macro_rules! point_binop {
+    ($point:ident { $($field:ident),+ }, $imp:ident, $method:ident, $assign:ident, $assign_method:ident) => {
+        point_binop!(@point $point { $($field),+ }, $imp, $method, $assign, $assign_method);
 
-impl std::fmt::Display for Point {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "({},{})", self.x, self.y)
-    }
+        impl<T: $imp<Output = T> + Copy> $imp<T> for $point<T> {
+            type Output = Self;
+
+            fn $method(self, rhs: T) -> Self::Output {
+                $point {
+                    $($field: $imp::$method(self.$field, rhs)),+
+                }
+            }
+        }
+
+        impl<T: $imp<Output = T> + Copy> $assign<T> for $point<T> {
+            fn $assign_method(&mut self, rhs: T) {
+                *self = $imp::$method(*self, rhs);
+            }
+        }
+    };
+    (@point $point:ident { $($field:ident),+ }, $imp:ident, $method:ident, $assign:ident, $assign_method:ident) => {
+        impl<T: $imp<Output = T>> $imp for $point<T> {
+            type Output = Self;
+
+            fn $method(self, rhs: Self) -> Self::Output {
+                $point {
+                    $($field: $imp::$method(self.$field, rhs.$field)),+
+                }
+            }
+        }
+
+        impl<T: $imp<Output = T> + Copy> $assign for $point<T> {
+            fn $assign_method(&mut self, rhs: Self) {
+                *self = $imp::$method(*self, rhs);
+            }
+        }
+    };
 }
 
-impl Point {
-    pub fn new(x: usize, y: usize) -> Self {
-        Point { x, y }
-    }
+/// Implements the arithmetic shared by every point type. Addition and
+/// subtraction only work between points, while multiplication, division and
+/// the modulo operators also accept a scalar.
+macro_rules! point_ops {
+    ($point:ident { $($field:ident),+ }) => {
+        point_binop!(@point $point { $($field),+ }, Add, add, AddAssign, add_assign);
+        point_binop!(@point $point { $($field),+ }, Sub, sub, SubAssign, sub_assign);
+        point_binop!($point { $($field),+ }, Mul, mul, MulAssign, mul_assign);
+        point_binop!($point { $($field),+ }, Div, div, DivAssign, div_assign);
+        point_binop!($point { $($field),+ }, Modulo, modulo, ModuloAssign, modulo_assign);
+        point_binop!(
+            $point { $($field),+ },
+            ModuloPositive,
+            modulo_positive,
+            ModuloPositiveAssign,
+            module_positive_assign
+        );
 
-    pub fn from_ipoint(ipoint: ipoint::IPoint) -> Self {
-        Point {
-            x: ipoint.x as usize,
-            y: ipoint.y as usize,
+        impl<T: Neg<Output = T>> Neg for $point<T> {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                $point {
+                    $($field: -self.$field),+
+                }
+            }
         }
-    }
 
-    pub fn checked_sub(&self, rhs: Self) -> Option<Self> {
-        if let Some(x) = self.x.checked_sub(rhs.x) {
-            if let Some(y) = self.y.checked_sub(rhs.y) {
-                return Some(Point::new(x, y));
+        impl<T: Absolute> Absolute for $point<T> {
+            fn absolute(self) -> Self {
+                $point {
+                    $($field: self.$field.absolute()),+
+                }
             }
         }
 
-        None
-    }
+        impl<T: Add<Output = T> + Default> Sum for $point<T> {
+            fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+                iter.fold($point::default(), |acc, item| acc + item)
+            }
+        }
+
+        impl<T: Integer> $point<T> {
+            pub fn checked_add(&self, rhs: Self) -> Option<Self> {
+                Some($point {
+                    $($field: self.$field.checked_add(rhs.$field)?),+
+                })
+            }
 
-    pub fn checked_add(&self, rhs: Self) -> Option<Self> {
-        if let Some(x) = self.x.checked_add(rhs.x) {
-            if let Some(y) = self.y.checked_add(rhs.y) {
-                return Some(Point::new(x, y));
+            pub fn checked_sub(&self, rhs: Self) -> Option<Self> {
+                Some($point {
+                    $($field: self.$field.checked_sub(rhs.$field)?),+
+                })
             }
         }
 
-        None
-    }
+        impl<I, T> Parseable<$point<T>> for I
+        where
+            I: Iterator<Item = u8> + Parseable<T>,
+        {
+            fn next_number(&mut self) -> Option<$point<T>> {
+                Some($point {
+                    $($field: Parseable::<T>::next_number(self)?),+
+                })
+            }
+        }
+    };
 }
 
-impl Add for &Point {
-    type Output = Point;
+/// Implements the operators of a point type for references to it, for each of
+/// the given component types.
+macro_rules! point_forward_ref {
+    ($point:ident, $($type:ty),+) => {
+        $(
+            forward_ref_binop!(impl Add, add for $point<$type>, $point<$type>);
+            forward_ref_binop!(impl Sub, sub for $point<$type>, $point<$type>);
+            forward_ref_binop!(impl Mul, mul for $point<$type>, $point<$type>);
+            forward_ref_binop!(impl Div, div for $point<$type>, $point<$type>);
+            forward_ref_binop!(impl Modulo, modulo for $point<$type>, $point<$type>);
+            forward_ref_binop!(impl ModuloPositive, modulo_positive for $point<$type>, $point<$type>);
+            forward_ref_binop!(impl Mul, mul for $point<$type>, $type);
+            forward_ref_binop!(impl Div, div for $point<$type>, $type);
+            forward_ref_binop!(impl Modulo, modulo for $point<$type>, $type);
+            forward_ref_binop!(impl ModuloPositive, modulo_positive for $point<$type>, $type);
+            forward_ref_op_assign!(impl AddAssign, add_assign for $point<$type>, $point<$type>);
+            forward_ref_op_assign!(impl SubAssign, sub_assign for $point<$type>, $point<$type>);
+            forward_ref_op_assign!(impl MulAssign, mul_assign for $point<$type>, $point<$type>);
+            forward_ref_op_assign!(impl DivAssign, div_assign for $point<$type>, $point<$type>);
+            forward_ref_op_assign!(impl ModuloAssign, modulo_assign for $point<$type>, $point<$type>);
+            forward_ref_op_assign!(impl ModuloPositiveAssign, module_positive_assign for $point<$type>, $point<$type>);
+            forward_ref_op_assign!(impl MulAssign, mul_assign for $point<$type>, $type);
+            forward_ref_op_assign!(impl DivAssign, div_assign for $point<$type>, $type);
+            forward_ref_op_assign!(impl ModuloAssign, modulo_assign for $point<$type>, $type);
+            forward_ref_op_assign!(impl ModuloPositiveAssign, module_positive_assign for $point<$type>, $type);
+        )+
+    };
+}
 
-    fn add(self, rhs: Self) -> Self::Output {
-        Point {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-        }
-    }
+/// The signed component types, which can also be negated by reference.
+macro_rules! point_forward_ref_neg {
+    ($point:ident, $($type:ty),+) => {
+        $(
+            forward_ref_unop!(impl Neg, neg for $point<$type>);
+        )+
+    };
 }
 
-impl Add for Point {
-    type Output = Point;
+pub mod hex;
+pub mod ipoint;
+pub mod line;
+pub mod point3;
+pub mod pointn;
+pub mod traits;
 
-    fn add(self, rhs: Self) -> Self::Output {
-        Point {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-        }
-    }
+/// A point on a plane with integer components of type `T`.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug, PartialOrd, Ord, Hash)]
+pub struct Point2<T> {
+    pub x: T,
+    pub y: T,
 }
 
-impl Add<Point> for &Point {
-    type Output = Point;
+/// A point on a grid, where `(0, 0)` is the top-left corner.
+pub type Point = Point2<usize>;
 
-    fn add(self, rhs: Point) -> Self::Output {
-        Point {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
-        }
+impl<T: fmt::Display> fmt::Display for Point2<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({},{})", self.x, self.y)
     }
 }
 
-impl Add<&Point> for Point {
-    type Output = Point;
+impl<T> Point2<T> {
+    pub fn new(x: T, y: T) -> Self {
+        Point2 { x, y }
+    }
 
-    fn add(self, rhs: &Point) -> Self::Output {
-        Point {
-            x: self.x + rhs.x,
-            y: self.y + rhs.y,
+    /// Converts each component into a type that can represent every value.
+    pub fn cast<U: From<T>>(self) -> Point2<U> {
+        Point2 {
+            x: self.x.into(),
+            y: self.y.into(),
         }
     }
-}
 
-impl Sub for Point {
-    type Output = Point;
+    /// Converts each component, or returns `None` if either does not fit.
+    pub fn try_cast<U: TryFrom<T>>(self) -> Option<Point2<U>> {
+        Some(Point2 {
+            x: self.x.try_into().ok()?,
+            y: self.y.try_into().ok()?,
+        })
+    }
+}
 
-    fn sub(self, rhs: Self) -> Self::Output {
-        Point {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
-        }
+impl Point {
+    /// Converts a signed point that is known to be non-negative. Prefer
+    /// [`Point::try_from`] or [`ipoint::IPoint::to_point_in`] when it may not be.
+    ///
+    /// # Panics
+    ///
+    /// If either component is negative.
+    pub fn from_ipoint(ipoint: ipoint::IPoint) -> Self {
+        Self::try_from(ipoint).unwrap_or_else(|e| panic!("{}", e))
     }
 }
 
-impl Sub for &Point {
-    type Output = Point;
+/// An error which can be returned when converting between [`Point`] and
+/// [`ipoint::IPoint`], holding the coordinates that did not fit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointConversionError {
+    Negative(ipoint::IPoint),
+    Overflow(Point),
+}
+
+impl Error for PointConversionError {}
 
-    fn sub(self, rhs: Self) -> Self::Output {
-        Point {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
+impl fmt::Display for PointConversionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PointConversionError::Negative(p) => write!(f, "{} has negative coordinates", p),
+            PointConversionError::Overflow(p) => write!(f, "{} does not fit in an IPoint", p),
         }
     }
 }
 
-impl Sub<Point> for &Point {
-    type Output = Point;
+impl TryFrom<ipoint::IPoint> for Point {
+    type Error = PointConversionError;
 
-    fn sub(self, rhs: Point) -> Self::Output {
-        Point {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
-        }
+    fn try_from(value: ipoint::IPoint) -> Result<Self, Self::Error> {
+        value
+            .try_cast()
+            .ok_or(PointConversionError::Negative(value))
     }
 }
 
-impl Sub<&Point> for Point {
-    type Output = Point;
+impl TryFrom<Point> for ipoint::IPoint {
+    type Error = PointConversionError;
 
-    fn sub(self, rhs: &Point) -> Self::Output {
-        Point {
-            x: self.x - rhs.x,
-            y: self.y - rhs.y,
-        }
+    fn try_from(value: Point) -> Result<Self, Self::Error> {
+        value
+            .try_cast()
+            .ok_or(PointConversionError::Overflow(value))
     }
 }
 
-impl<T: Iterator<Item = u8>> Parseable<Point> for T {
-    fn next_number(&mut self) -> Option<Point> {
-        if let Some((x, y)) = self.next_number().zip(self.next_number()) {
-            return Some(Point::new(x, y));
-        }
-        None
+point_ops!(Point2 { x, y });
+point_forward_ref!(Point2, u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize);
+point_forward_ref_neg!(Point2, i8, i16, i32, i64, i128, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::{ipoint::IPoint, point3::Point3, *};
+
+    #[test]
+    fn test_ops() {
+        let a = IPoint::new(7, -3);
+        let b = IPoint::new(2, 5);
+        assert_eq!(a + b, IPoint::new(9, 2));
+        assert_eq!(a - b, IPoint::new(5, -8));
+        assert_eq!(a * b, IPoint::new(14, -15));
+        assert_eq!(a * 2, IPoint::new(14, -6));
+        assert_eq!(a / 2, IPoint::new(3, -1));
+        assert_eq!(a.modulo(4), IPoint::new(3, -3));
+        assert_eq!(a.modulo_positive(4), IPoint::new(3, 1));
+        assert_eq!(-a, IPoint::new(-7, 3));
+        assert_eq!(a.absolute(), IPoint::new(7, 3));
+        assert_eq!([a, b, -b].into_iter().sum::<IPoint>(), a);
+
+        let mut c = a;
+        c += b;
+        c *= 3;
+        c -= IPoint::new(1, 1);
+        assert_eq!(c, IPoint::new(26, 5));
+
+        assert_eq!(Point3::new(1, 2, 3) * 2 - Point3::new(2, 2, 2), Point3::new(0, 2, 4));
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn test_ref_ops() {
+        let a = Point::new(6, 4);
+        let b = Point::new(1, 2);
+        assert_eq!(&a + &b, a + b);
+        assert_eq!(&a - b, a - b);
+        assert_eq!(a / &b, a / b);
+        assert_eq!(&a * &3, a * 3);
+        assert_eq!(-&IPoint::new(1, -1), IPoint::new(-1, 1));
+
+        let mut c = a;
+        c.modulo_assign(&b);
+        assert_eq!(c, Point::new(0, 0));
+    }
+
+    #[test]
+    fn test_checked() {
+        let max = Point2::new(u8::MAX, 0);
+        assert_eq!(max.checked_add(Point2::new(0, 1)), Some(Point2::new(u8::MAX, 1)));
+        assert_eq!(max.checked_add(Point2::new(1, 0)), None);
+        assert_eq!(Point::new(1, 1).checked_sub(Point::new(0, 2)), None);
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(IPoint::new(3, 4).try_cast::<u8>(), Some(Point2::new(3, 4)));
+        assert_eq!(IPoint::new(3, -4).try_cast::<usize>(), None);
+        assert_eq!(IPoint::new(300, 4).try_cast::<u8>(), None);
+        assert_eq!(Point2::<u8>::new(3, 4).cast::<i32>(), Point2::new(3, 4));
+
+        assert_eq!(Point::try_from(IPoint::new(1, 2)), Ok(Point::new(1, 2)));
+        assert_eq!(
+            Point::try_from(IPoint::new(1, -2)),
+            Err(PointConversionError::Negative(IPoint::new(1, -2)))
+        );
+        let huge = Point::new(usize::MAX, 0);
+        assert_eq!(IPoint::try_from(huge), Err(PointConversionError::Overflow(huge)));
+    }
+
+    #[test]
+    #[should_panic(expected = "(-1,0) has negative coordinates")]
+    fn test_from_ipoint_negative() {
+        Point::from_ipoint(IPoint::new(-1, 0));
+    }
+
+    #[test]
+    #[should_panic(expected = "does not fit in an IPoint")]
+    fn test_from_point_overflow() {
+        IPoint::from_point(Point::new(0, usize::MAX));
     }
 }
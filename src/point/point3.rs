@@ -0,0 +1,61 @@
+use core::fmt;
+use std::{
+    iter::Sum,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use forward_ref::{forward_ref_binop, forward_ref_op_assign, forward_ref_unop};
+
+use crate::parse::Parseable;
+
+use super::{
+    traits::{Absolute, Integer, Modulo, ModuloAssign, ModuloPositive, ModuloPositiveAssign},
+    Point2,
+};
+
+/// A point in space with integer components of type `T`.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug, PartialOrd, Ord, Hash)]
+pub struct Point3<T> {
+    pub x: T,
+    pub y: T,
+    pub z: T,
+}
+
+impl<T: fmt::Display> fmt::Display for Point3<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "({},{},{})", self.x, self.y, self.z)
+    }
+}
+
+impl<T> Point3<T> {
+    pub fn new(x: T, y: T, z: T) -> Self {
+        Point3 { x, y, z }
+    }
+
+    /// Converts each component into a type that can represent every value.
+    pub fn cast<U: From<T>>(self) -> Point3<U> {
+        Point3 {
+            x: self.x.into(),
+            y: self.y.into(),
+            z: self.z.into(),
+        }
+    }
+
+    /// Converts each component, or returns `None` if any does not fit.
+    pub fn try_cast<U: TryFrom<T>>(self) -> Option<Point3<U>> {
+        Some(Point3 {
+            x: self.x.try_into().ok()?,
+            y: self.y.try_into().ok()?,
+            z: self.z.try_into().ok()?,
+        })
+    }
+
+    /// Drops the `z` component.
+    pub fn xy(self) -> Point2<T> {
+        Point2::new(self.x, self.y)
+    }
+}
+
+point_ops!(Point3 { x, y, z });
+point_forward_ref!(Point3, u8, i8, u16, i16, u32, i32, u64, i64, u128, i128, usize, isize);
+point_forward_ref_neg!(Point3, i8, i16, i32, i64, i128, isize);
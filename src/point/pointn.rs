@@ -0,0 +1,248 @@
+use core::fmt;
+use std::{
+    array,
+    iter::Sum,
+    ops::{Add, AddAssign, Div, DivAssign, Index, IndexMut, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+use crate::parse::Parseable;
+
+use super::traits::{
+    Absolute, Integer, Modulo, ModuloAssign, ModuloPositive, ModuloPositiveAssign,
+};
+
+/// A point with `N` integer components of type `T`.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, PartialOrd, Ord, Hash)]
+pub struct PointN<T, const N: usize>(pub [T; N]);
+
+fn all_some<T, const N: usize>(values: [Option<T>; N]) -> Option<[T; N]> {
+    if values.iter().any(Option::is_none) {
+        return None;
+    }
+    Some(values.map(Option::unwrap))
+}
+
+impl<T: Default, const N: usize> Default for PointN<T, N> {
+    fn default() -> Self {
+        PointN(array::from_fn(|_| T::default()))
+    }
+}
+
+impl<T: fmt::Display, const N: usize> fmt::Display for PointN<T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "(")?;
+        for (i, value) in self.0.iter().enumerate() {
+            if i > 0 {
+                write!(f, ",")?;
+            }
+            write!(f, "{}", value)?;
+        }
+        write!(f, ")")
+    }
+}
+
+impl<T, const N: usize> PointN<T, N> {
+    pub fn new(components: [T; N]) -> Self {
+        PointN(components)
+    }
+
+    /// Converts each component into a type that can represent every value.
+    pub fn cast<U: From<T>>(self) -> PointN<U, N> {
+        PointN(self.0.map(U::from))
+    }
+
+    /// Converts each component, or returns `None` if any does not fit.
+    pub fn try_cast<U: TryFrom<T>>(self) -> Option<PointN<U, N>> {
+        all_some(self.0.map(|v| U::try_from(v).ok())).map(PointN)
+    }
+}
+
+impl<T: Integer, const N: usize> PointN<T, N> {
+    pub fn checked_add(&self, rhs: Self) -> Option<Self> {
+        all_some(array::from_fn(|i| self.0[i].checked_add(rhs.0[i]))).map(PointN)
+    }
+
+    pub fn checked_sub(&self, rhs: Self) -> Option<Self> {
+        all_some(array::from_fn(|i| self.0[i].checked_sub(rhs.0[i]))).map(PointN)
+    }
+}
+
+impl<T, const N: usize> Index<usize> for PointN<T, N> {
+    type Output = T;
+
+    fn index(&self, index: usize) -> &T {
+        &self.0[index]
+    }
+}
+
+impl<T, const N: usize> IndexMut<usize> for PointN<T, N> {
+    fn index_mut(&mut self, index: usize) -> &mut T {
+        &mut self.0[index]
+    }
+}
+
+/// Implements an operator for references to points, with a right-hand side
+/// of type `$rhs`. The `forward_ref` macros only handle concrete types, so
+/// cannot be used with a generic length.
+macro_rules! pointn_forward_ref {
+    ($imp:ident, $method:ident, $assign:ident, $assign_method:ident, $rhs:ty) => {
+        impl<T: $imp<Output = T> + Copy, const N: usize> $imp<&$rhs> for PointN<T, N> {
+            type Output = Self;
+
+            fn $method(self, rhs: &$rhs) -> Self::Output {
+                $imp::$method(self, *rhs)
+            }
+        }
+
+        impl<T: $imp<Output = T> + Copy, const N: usize> $imp<$rhs> for &PointN<T, N> {
+            type Output = PointN<T, N>;
+
+            fn $method(self, rhs: $rhs) -> Self::Output {
+                $imp::$method(*self, rhs)
+            }
+        }
+
+        impl<T: $imp<Output = T> + Copy, const N: usize> $imp<&$rhs> for &PointN<T, N> {
+            type Output = PointN<T, N>;
+
+            fn $method(self, rhs: &$rhs) -> Self::Output {
+                $imp::$method(*self, *rhs)
+            }
+        }
+
+        impl<T: $imp<Output = T> + Copy, const N: usize> $assign<&$rhs> for PointN<T, N> {
+            fn $assign_method(&mut self, rhs: &$rhs) {
+                *self = $imp::$method(*self, *rhs);
+            }
+        }
+    };
+}
+
+macro_rules! pointn_binop {
+    ($imp:ident, $method:ident, $assign:ident, $assign_method:ident) => {
+        pointn_binop!(@point $imp, $method, $assign, $assign_method);
+        pointn_forward_ref!($imp, $method, $assign, $assign_method, T);
+
+        impl<T: $imp<Output = T> + Copy, const N: usize> $imp<T> for PointN<T, N> {
+            type Output = Self;
+
+            fn $method(self, rhs: T) -> Self::Output {
+                PointN(self.0.map(|v| $imp::$method(v, rhs)))
+            }
+        }
+
+        impl<T: $imp<Output = T> + Copy, const N: usize> $assign<T> for PointN<T, N> {
+            fn $assign_method(&mut self, rhs: T) {
+                *self = $imp::$method(*self, rhs);
+            }
+        }
+    };
+    (@point $imp:ident, $method:ident, $assign:ident, $assign_method:ident) => {
+        pointn_forward_ref!($imp, $method, $assign, $assign_method, PointN<T, N>);
+
+        impl<T: $imp<Output = T> + Copy, const N: usize> $imp for PointN<T, N> {
+            type Output = Self;
+
+            fn $method(self, rhs: Self) -> Self::Output {
+                PointN(array::from_fn(|i| $imp::$method(self.0[i], rhs.0[i])))
+            }
+        }
+
+        impl<T: $imp<Output = T> + Copy, const N: usize> $assign for PointN<T, N> {
+            fn $assign_method(&mut self, rhs: Self) {
+                *self = $imp::$method(*self, rhs);
+            }
+        }
+    };
+}
+
+pointn_binop!(@point Add, add, AddAssign, add_assign);
+pointn_binop!(@point Sub, sub, SubAssign, sub_assign);
+pointn_binop!(Mul, mul, MulAssign, mul_assign);
+pointn_binop!(Div, div, DivAssign, div_assign);
+pointn_binop!(Modulo, modulo, ModuloAssign, modulo_assign);
+pointn_binop!(
+    ModuloPositive,
+    modulo_positive,
+    ModuloPositiveAssign,
+    module_positive_assign
+);
+
+impl<T: Neg<Output = T>, const N: usize> Neg for PointN<T, N> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        PointN(self.0.map(|v| -v))
+    }
+}
+
+impl<T: Neg<Output = T> + Copy, const N: usize> Neg for &PointN<T, N> {
+    type Output = PointN<T, N>;
+
+    fn neg(self) -> Self::Output {
+        -*self
+    }
+}
+
+impl<T: Absolute, const N: usize> Absolute for PointN<T, N> {
+    fn absolute(self) -> Self {
+        PointN(self.0.map(Absolute::absolute))
+    }
+}
+
+impl<T: Add<Output = T> + Copy + Default, const N: usize> Sum for PointN<T, N> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(PointN::default(), |acc, item| acc + item)
+    }
+}
+
+impl<I, T, const N: usize> Parseable<PointN<T, N>> for I
+where
+    I: Iterator<Item = u8> + Parseable<T>,
+{
+    fn next_number(&mut self) -> Option<PointN<T, N>> {
+        all_some(array::from_fn(|_| Parseable::<T>::next_number(self))).map(PointN)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn test_ops() {
+        let a = PointN([4i32, -2, 7, 0]);
+        let b = PointN([1, 1, -3, 5]);
+        assert_eq!(a + b, PointN([5, -1, 4, 5]));
+        assert_eq!(&a - &b, PointN([3, -3, 10, -5]));
+        assert_eq!(&a * 2, PointN([8, -4, 14, 0]));
+        assert_eq!(a.modulo_positive(&3), PointN([1, 1, 1, 0]));
+        assert_eq!(-&a, PointN([-4, 2, -7, 0]));
+        assert_eq!([a, b].iter().copied().sum::<PointN<i32, 4>>(), a + b);
+
+        let mut c = a;
+        c += &b;
+        c /= &2;
+        assert_eq!(c, PointN([2, 0, 2, 2]));
+        assert_eq!(c[3], 2);
+    }
+
+    #[test]
+    fn test_conversions() {
+        let a = PointN([1i64, -1]);
+        assert_eq!(a.try_cast::<u8>(), None);
+        assert_eq!(a.try_cast::<i8>(), Some(PointN([1, -1])));
+        assert_eq!(PointN([3u8, 4]).cast::<u32>(), PointN([3, 4]));
+        assert_eq!(PointN([i8::MAX, 0]).checked_add(PointN([1, 0])), None);
+        assert_eq!(a.to_string(), "(1,-1)");
+    }
+
+    #[test]
+    fn test_parse() {
+        let mut bytes = "1,-2,3 @ 40,5,-60\n7,8".bytes();
+        assert_eq!(bytes.next_number(), Some(PointN([1i64, -2, 3])));
+        assert_eq!(bytes.next_number(), Some(PointN([40i64, 5, -60])));
+        assert_eq!(Parseable::<PointN<i64, 3>>::next_number(&mut bytes), None);
+    }
+}
@@ -1,3 +1,5 @@
+use std::ops::{Add, Div, Mul, Rem, Sub};
+
 pub trait Modulo<Rhs = Self> {
     type Output;
 
@@ -21,3 +23,129 @@ pub trait ModuloPositive<Rhs = Self> {
 pub trait ModuloPositiveAssign<Rhs = Self> {
     fn module_positive_assign(&mut self, rhs: Rhs);
 }
+
+/// The primitive integer types that points can be built from.
+pub trait Integer:
+    Copy
+    + Ord
+    + Default
+    + Add<Output = Self>
+    + Sub<Output = Self>
+    + Mul<Output = Self>
+    + Div<Output = Self>
+    + Rem<Output = Self>
+    + Modulo<Output = Self>
+    + ModuloPositive<Output = Self>
+    + Absolute
+{
+    const ZERO: Self;
+    const ONE: Self;
+
+    fn checked_add(self, rhs: Self) -> Option<Self>;
+    fn checked_sub(self, rhs: Self) -> Option<Self>;
+    fn checked_mul(self, rhs: Self) -> Option<Self>;
+}
+
+macro_rules! Integer_unsigned {
+    ($type:ident) => {
+        impl Integer for $type {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                $type::checked_add(self, rhs)
+            }
+
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                $type::checked_sub(self, rhs)
+            }
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                $type::checked_mul(self, rhs)
+            }
+        }
+
+        impl Modulo for $type {
+            type Output = $type;
+
+            fn modulo(self, rhs: Self) -> Self::Output {
+                self % rhs
+            }
+        }
+
+        impl ModuloPositive for $type {
+            type Output = $type;
+
+            fn modulo_positive(self, rhs: Self) -> Self::Output {
+                self % rhs
+            }
+        }
+
+        impl Absolute for $type {
+            fn absolute(self) -> Self {
+                self
+            }
+        }
+    };
+}
+
+macro_rules! Integer_signed {
+    ($type:ident) => {
+        impl Integer for $type {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+
+            fn checked_add(self, rhs: Self) -> Option<Self> {
+                $type::checked_add(self, rhs)
+            }
+
+            fn checked_sub(self, rhs: Self) -> Option<Self> {
+                $type::checked_sub(self, rhs)
+            }
+
+            fn checked_mul(self, rhs: Self) -> Option<Self> {
+                $type::checked_mul(self, rhs)
+            }
+        }
+
+        impl Modulo for $type {
+            type Output = $type;
+
+            fn modulo(self, rhs: Self) -> Self::Output {
+                self % rhs
+            }
+        }
+
+        impl ModuloPositive for $type {
+            type Output = $type;
+
+            fn modulo_positive(self, rhs: Self) -> Self::Output {
+                let value = self % rhs;
+                if value < 0 {
+                    value + rhs
+                } else {
+                    value
+                }
+            }
+        }
+
+        impl Absolute for $type {
+            fn absolute(self) -> Self {
+                self.abs()
+            }
+        }
+    };
+}
+
+Integer_unsigned!(u8);
+Integer_signed!(i8);
+Integer_unsigned!(u16);
+Integer_signed!(i16);
+Integer_unsigned!(u32);
+Integer_signed!(i32);
+Integer_unsigned!(u64);
+Integer_signed!(i64);
+Integer_unsigned!(u128);
+Integer_signed!(i128);
+Integer_unsigned!(usize);
+Integer_signed!(isize);
@@ -0,0 +1,272 @@
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::template::Day;
+
+use super::PointMap;
+
+pub type Rgb = [u8; 3];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImageFormat {
+    /// Binary greyscale netpbm, using the average of each colour.
+    Pgm,
+    /// Binary colour netpbm.
+    Ppm,
+    /// Uncompressed PNG.
+    Png,
+}
+
+impl ImageFormat {
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Pgm => "pgm",
+            ImageFormat::Ppm => "ppm",
+            ImageFormat::Png => "png",
+        }
+    }
+}
+
+impl<T> PointMap<T> {
+    /// Encodes the map as an image with one square of `scale` pixels per cell.
+    ///
+    /// # Panics
+    ///
+    /// If `scale` is zero.
+    pub fn to_image(
+        &self,
+        format: ImageFormat,
+        scale: usize,
+        colour: impl Fn(&T) -> Rgb,
+    ) -> Vec<u8> {
+        assert!(scale >= 1, "image scale must be at least 1");
+        let width = self.width * scale;
+        let height = self.height * scale;
+        let pixels = (0..height).flat_map(|y| {
+            let row = self.row(y / scale);
+            (0..width).map(move |x| &row[x / scale])
+        });
+
+        match format {
+            ImageFormat::Pgm => {
+                let mut out = format!("P5\n{} {}\n255\n", width, height).into_bytes();
+                out.extend(pixels.map(|cell| grey(colour(cell))));
+                out
+            }
+            ImageFormat::Ppm => {
+                let mut out = format!("P6\n{} {}\n255\n", width, height).into_bytes();
+                out.extend(pixels.flat_map(&colour));
+                out
+            }
+            ImageFormat::Png => {
+                let mut raw = Vec::with_capacity(height * (width * 3 + 1));
+                for (i, cell) in pixels.enumerate() {
+                    if i % width == 0 {
+                        // each scanline starts with its filter type, which is none
+                        raw.push(0);
+                    }
+                    raw.extend(colour(cell));
+                }
+                png(width as u32, height as u32, &raw)
+            }
+        }
+    }
+
+    pub fn write_image(
+        &self,
+        path: impl AsRef<Path>,
+        format: ImageFormat,
+        scale: usize,
+        colour: impl Fn(&T) -> Rgb,
+    ) -> io::Result<()> {
+        fs::write(path, self.to_image(format, scale, colour))
+    }
+}
+
+fn grey([r, g, b]: Rgb) -> u8 {
+    ((r as u16 + g as u16 + b as u16) / 3) as u8
+}
+
+/// Writes the successive states of a simulation as numbered image files,
+/// by default to `data/frames/<day>/`.
+pub struct FrameSink {
+    dir: PathBuf,
+    format: ImageFormat,
+    scale: usize,
+    next: usize,
+}
+
+impl FrameSink {
+    pub fn new(day: Day) -> io::Result<Self> {
+        let dir = env::current_dir()?
+            .join("data")
+            .join("frames")
+            .join(day.to_string());
+        Self::with_dir(dir)
+    }
+
+    pub fn with_dir(dir: impl Into<PathBuf>) -> io::Result<Self> {
+        let dir = dir.into();
+        fs::create_dir_all(&dir)?;
+        Ok(Self {
+            dir,
+            format: ImageFormat::Png,
+            scale: 1,
+            next: 0,
+        })
+    }
+
+    pub fn format(mut self, format: ImageFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// # Panics
+    ///
+    /// If `scale` is zero.
+    pub fn scale(mut self, scale: usize) -> Self {
+        assert!(scale >= 1, "image scale must be at least 1");
+        self.scale = scale;
+        self
+    }
+
+    /// Writes the next frame, returning the path it was written to.
+    pub fn write<T>(
+        &mut self,
+        map: &PointMap<T>,
+        colour: impl Fn(&T) -> Rgb,
+    ) -> io::Result<PathBuf> {
+        let path = self
+            .dir
+            .join(format!("{:06}.{}", self.next, self.format.extension()));
+        map.write_image(&path, self.format, self.scale, colour)?;
+        self.next += 1;
+        Ok(path)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+fn png(width: u32, height: u32, raw: &[u8]) -> Vec<u8> {
+    let mut out = b"\x89PNG\r\n\x1a\n".to_vec();
+
+    let mut header = Vec::with_capacity(13);
+    header.extend(width.to_be_bytes());
+    header.extend(height.to_be_bytes());
+    // 8 bits per channel, RGB, default compression, filtering and no interlacing
+    header.extend([8, 2, 0, 0, 0]);
+    png_chunk(&mut out, b"IHDR", &header);
+    png_chunk(&mut out, b"IDAT", &zlib_stored(raw));
+    png_chunk(&mut out, b"IEND", &[]);
+
+    out
+}
+
+fn png_chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+    out.extend((data.len() as u32).to_be_bytes());
+    let start = out.len();
+    out.extend(kind);
+    out.extend(data);
+    let crc = crc32(&out[start..]);
+    out.extend(crc.to_be_bytes());
+}
+
+/// Wraps `data` in a zlib stream made of uncompressed deflate blocks.
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = 0xffff;
+
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        out.extend([1, 0, 0, 0xff, 0xff]);
+    }
+    while let Some(block) = blocks.next() {
+        let last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        out.push(last as u8);
+        out.extend(len.to_le_bytes());
+        out.extend((!len).to_le_bytes());
+        out.extend(block);
+    }
+    out.extend(adler32(data).to_be_bytes());
+
+    out
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xedb8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+
+    let (mut a, mut b) = (1u32, 0u32);
+    for byte in data {
+        a = (a + *byte as u32) % MOD;
+        b = (b + a) % MOD;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checksums() {
+        assert_eq!(crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(crc32(b""), 0);
+        assert_eq!(adler32(b"Wikipedia"), 0x11e6_0398);
+        assert_eq!(adler32(b""), 1);
+    }
+
+    #[test]
+    fn test_zlib_stored() {
+        assert_eq!(zlib_stored(&[]), [0x78, 0x01, 1, 0, 0, 0xff, 0xff, 0, 0, 0, 1]);
+
+        let data = vec![7; 0x10000 + 2];
+        let out = zlib_stored(&data);
+        assert_eq!(out.len(), 2 + (5 + 0xffff) + (5 + 3) + 4);
+        assert_eq!(out[2..7], [0, 0xff, 0xff, 0, 0]);
+        let second = 7 + 0xffff;
+        assert_eq!(out[second..second + 5], [1, 3, 0, 0xfc, 0xff]);
+        assert_eq!(out[out.len() - 4..], adler32(&data).to_be_bytes());
+    }
+
+    #[test]
+    fn test_netpbm() {
+        let map = PointMap::new(vec![[30, 60, 90], [255, 255, 255]], 2, 1);
+
+        let pgm = map.to_image(ImageFormat::Pgm, 2, |c| *c);
+        assert_eq!(pgm, b"P5\n4 2\n255\n\x3c\x3c\xff\xff\x3c\x3c\xff\xff");
+
+        let ppm = map.to_image(ImageFormat::Ppm, 1, |c| *c);
+        assert_eq!(ppm, b"P6\n2 1\n255\n\x1e\x3c\x5a\xff\xff\xff");
+    }
+
+    #[test]
+    fn test_png() {
+        let map = PointMap::new(vec![[1, 2, 3]; 6], 3, 2);
+        let png = map.to_image(ImageFormat::Png, 1, |c| *c);
+        assert_eq!(png[..8], *b"\x89PNG\r\n\x1a\n");
+        assert_eq!(png[12..16], *b"IHDR");
+        assert_eq!(png[16..24], [0, 0, 0, 3, 0, 0, 0, 2]);
+        assert_eq!(png[png.len() - 12..], *b"\0\0\0\0IEND\xae\x42\x60\x82");
+    }
+
+    #[test]
+    #[should_panic(expected = "image scale must be at least 1")]
+    fn test_zero_scale() {
+        PointMap::new(vec![[0; 3]], 1, 1).to_image(ImageFormat::Pgm, 0, |c| *c);
+    }
+}
@@ -1,6 +1,20 @@
-use std::ops::{Index, IndexMut};
+use std::{
+    fmt,
+    ops::{Index, IndexMut},
+};
 
-use crate::point::{Point, ipoint::IPoint};
+use crate::{
+    point::{Point, ipoint::IPoint},
+    rect::Rect,
+};
+
+pub mod image;
+pub mod parse;
+pub mod ray;
+pub mod regions;
+pub mod render;
+pub mod transform;
+pub mod wrapping;
 
 #[derive(Debug, Clone)]
 pub struct PointMap<T> {
@@ -9,7 +23,7 @@ pub struct PointMap<T> {
     pub height: usize,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub enum Direction {
     North,
     East,
@@ -18,6 +32,13 @@ pub enum Direction {
 }
 
 impl Direction {
+    pub const ALL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
     pub fn rotate_clockwise(&self) -> Self {
         match self {
             Direction::North => Self::East,
@@ -44,6 +65,16 @@ impl Direction {
             Direction::West => Self::East,
         }
     }
+
+    /// The unit offset of a single step in this direction, with y growing southwards.
+    pub fn offset(&self) -> IPoint {
+        match self {
+            Direction::North => IPoint::new(0, -1),
+            Direction::East => IPoint::new(1, 0),
+            Direction::South => IPoint::new(0, 1),
+            Direction::West => IPoint::new(-1, 0),
+        }
+    }
 }
 
 impl From<Direction> for usize {
@@ -52,6 +83,174 @@ impl From<Direction> for usize {
     }
 }
 
+impl From<Direction> for IPoint {
+    fn from(value: Direction) -> Self {
+        value.offset()
+    }
+}
+
+/// The eight compass directions, ordered clockwise from north.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction8 {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction8 {
+    pub const ALL: [Direction8; 8] = [
+        Direction8::North,
+        Direction8::NorthEast,
+        Direction8::East,
+        Direction8::SouthEast,
+        Direction8::South,
+        Direction8::SouthWest,
+        Direction8::West,
+        Direction8::NorthWest,
+    ];
+
+    fn turn(&self, eighths: usize) -> Self {
+        Self::ALL[(*self as usize + eighths) % 8]
+    }
+
+    pub fn rotate_clockwise_45(&self) -> Self {
+        self.turn(1)
+    }
+
+    pub fn rotate_counterclockwise_45(&self) -> Self {
+        self.turn(7)
+    }
+
+    pub fn rotate_clockwise(&self) -> Self {
+        self.turn(2)
+    }
+
+    pub fn rotate_counterclockwise(&self) -> Self {
+        self.turn(6)
+    }
+
+    pub fn reverse(&self) -> Self {
+        self.turn(4)
+    }
+
+    pub fn is_diagonal(&self) -> bool {
+        (*self as usize) % 2 == 1
+    }
+
+    /// The unit offset of a single step in this direction, with y growing southwards.
+    pub fn offset(&self) -> IPoint {
+        match self {
+            Direction8::North => IPoint::new(0, -1),
+            Direction8::NorthEast => IPoint::new(1, -1),
+            Direction8::East => IPoint::new(1, 0),
+            Direction8::SouthEast => IPoint::new(1, 1),
+            Direction8::South => IPoint::new(0, 1),
+            Direction8::SouthWest => IPoint::new(-1, 1),
+            Direction8::West => IPoint::new(-1, 0),
+            Direction8::NorthWest => IPoint::new(-1, -1),
+        }
+    }
+}
+
+impl From<Direction> for Direction8 {
+    fn from(value: Direction) -> Self {
+        match value {
+            Direction::North => Direction8::North,
+            Direction::East => Direction8::East,
+            Direction::South => Direction8::South,
+            Direction::West => Direction8::West,
+        }
+    }
+}
+
+impl TryFrom<Direction8> for Direction {
+    type Error = Direction8;
+
+    fn try_from(value: Direction8) -> Result<Self, Self::Error> {
+        match value {
+            Direction8::North => Ok(Direction::North),
+            Direction8::East => Ok(Direction::East),
+            Direction8::South => Ok(Direction::South),
+            Direction8::West => Ok(Direction::West),
+            _ => Err(value),
+        }
+    }
+}
+
+impl From<Direction8> for usize {
+    fn from(value: Direction8) -> Self {
+        value as usize
+    }
+}
+
+impl From<Direction8> for IPoint {
+    fn from(value: Direction8) -> Self {
+        value.offset()
+    }
+}
+
+/// A coordinate that can address a cell of a [`PointMap`], so lookups accept
+/// a signed [`IPoint`] without the caller converting it first.
+pub trait GridIndex: Copy + fmt::Display {
+    /// The cell this refers to, if it lies within a `width` by `height` grid.
+    fn to_grid_point(self, width: usize, height: usize) -> Option<Point>;
+}
+
+impl GridIndex for Point {
+    fn to_grid_point(self, width: usize, height: usize) -> Option<Point> {
+        (self.x < width && self.y < height).then_some(self)
+    }
+}
+
+impl GridIndex for IPoint {
+    fn to_grid_point(self, width: usize, height: usize) -> Option<Point> {
+        self.to_point_in(width, height)
+    }
+}
+
+/// Read access to a rectangular grid of cells, shared by [`PointMap`] and
+/// its [`Window`](transform::Window)s so that lookups and searches written
+/// against it work on either.
+pub trait Grid {
+    type Cell;
+
+    fn width(&self) -> usize;
+
+    fn height(&self) -> usize;
+
+    /// The cell at `point`, which must lie within the grid.
+    fn at(&self, point: Point) -> &Self::Cell;
+
+    /// The cell at `index`, or `None` if it lies outside the grid.
+    fn get<I: GridIndex>(&self, index: I) -> Option<&Self::Cell> {
+        let point = index.to_grid_point(self.width(), self.height())?;
+        Some(self.at(point))
+    }
+
+    fn contains<I: GridIndex>(&self, index: I) -> bool {
+        index.to_grid_point(self.width(), self.height()).is_some()
+    }
+
+    fn points(&self) -> Points {
+        Points {
+            height: self.height(),
+            width: self.width(),
+            x: 0,
+            y: 0,
+        }
+    }
+
+    /// Steps one cell in any of the eight directions, staying within the grid.
+    fn step8(&self, point: Point, dir: Direction8) -> Option<Point> {
+        (IPoint::from_point(point) + dir.offset()).to_point_in(self.width(), self.height())
+    }
+}
+
 pub struct Points {
     pub height: usize,
     pub width: usize,
@@ -96,12 +295,28 @@ impl<T> PointMap<T> {
         &self.vec[self.width * point.y + point.x]
     }
 
+    /// The area covered by the map, from the origin to `(width, height)`.
+    pub fn rect(&self) -> Rect<usize> {
+        Rect::from_size(self.width, self.height)
+    }
+
     pub fn ati(&self, point: IPoint) -> Option<&T> {
-        if point.in_bounds(self.width, self.height) {
-            Some(self.at(Point::from_ipoint(point)))
-        } else {
-            None
-        }
+        self.get(point)
+    }
+
+    /// The cell at `index`, or `None` if it lies outside the map.
+    pub fn get<I: GridIndex>(&self, index: I) -> Option<&T> {
+        let point = index.to_grid_point(self.width, self.height)?;
+        Some(self.at(point))
+    }
+
+    pub fn get_mut<I: GridIndex>(&mut self, index: I) -> Option<&mut T> {
+        let point = index.to_grid_point(self.width, self.height)?;
+        Some(self.at_mut(point))
+    }
+
+    pub fn contains<I: GridIndex>(&self, index: I) -> bool {
+        index.to_grid_point(self.width, self.height).is_some()
     }
 
     pub fn at_mut(&mut self, point: Point) -> &mut T {
@@ -112,12 +327,12 @@ impl<T> PointMap<T> {
         self.vec[self.width * point.y + point.x] = value;
     }
 
-    pub fn is_in_bounds(&mut self, point: Point) -> bool {
+    pub fn is_in_bounds(&self, point: Point) -> bool {
         point.x < self.width && point.y < self.height
     }
 
-    pub fn is_in_boundsi(&mut self, point: IPoint) -> bool {
-        point.in_bounds(self.width, self.height)
+    pub fn is_in_boundsi(&self, point: IPoint) -> bool {
+        self.contains(point)
     }
 
     pub fn step(&self, point: Point, dir: Direction) -> Option<Point> {
@@ -138,6 +353,16 @@ impl<T> PointMap<T> {
         ]
     }
 
+    /// Steps one cell in any of the eight directions, staying within the map.
+    pub fn step8(&self, point: Point, dir: Direction8) -> Option<Point> {
+        (IPoint::from_point(point) + dir.offset()).to_point_in(self.width, self.height)
+    }
+
+    /// The eight surrounding cells, in [`Direction8::ALL`] order.
+    pub fn neighbors8(&self, point: Point) -> [Option<Point>; 8] {
+        Direction8::ALL.map(|dir| self.step8(point, dir))
+    }
+
     pub fn step_north(&self, point: Point) -> Option<Point> {
         if point.y == 0 {
             None
@@ -178,16 +403,104 @@ impl<T> PointMap<T> {
     }
 }
 
-impl<T> Index<Point> for PointMap<T> {
-    fn index(&self, index: Point) -> &T {
-        self.at(index)
+impl<T> Grid for PointMap<T> {
+    type Cell = T;
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn at(&self, point: Point) -> &T {
+        PointMap::at(self, point)
+    }
+}
+
+impl<T, I: GridIndex> Index<I> for PointMap<T> {
+    fn index(&self, index: I) -> &T {
+        self.get(index)
+            .unwrap_or_else(|| panic!("{} is outside the map", index))
     }
 
     type Output = T;
 }
 
-impl<T> IndexMut<Point> for PointMap<T> {
-    fn index_mut(&mut self, index: Point) -> &mut T {
-        self.at_mut(index)
+impl<T, I: GridIndex> IndexMut<I> for PointMap<T> {
+    fn index_mut(&mut self, index: I) -> &mut T {
+        self.get_mut(index)
+            .unwrap_or_else(|| panic!("{} is outside the map", index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_direction8_turning() {
+        for dir in Direction8::ALL {
+            assert_eq!(dir.rotate_clockwise_45().rotate_counterclockwise_45(), dir);
+            assert_eq!(dir.rotate_clockwise().rotate_counterclockwise(), dir);
+            assert_eq!(dir.reverse().reverse(), dir);
+            assert_eq!(dir.reverse().offset(), -dir.offset());
+            assert_eq!(dir.is_diagonal(), !dir.rotate_clockwise_45().is_diagonal());
+        }
+        assert_eq!(Direction8::NorthWest.rotate_clockwise_45(), Direction8::North);
+        assert_eq!(Direction8::North.rotate_counterclockwise_45(), Direction8::NorthWest);
+        assert_eq!(Direction8::SouthWest.rotate_clockwise(), Direction8::NorthWest);
+        assert_eq!(Direction8::NorthEast.reverse(), Direction8::SouthWest);
+    }
+
+    #[test]
+    fn test_direction8_conversions() {
+        let diagonals: Vec<_> = Direction8::ALL.into_iter().filter(|d| d.is_diagonal()).collect();
+        assert_eq!(
+            diagonals,
+            [
+                Direction8::NorthEast,
+                Direction8::SouthEast,
+                Direction8::SouthWest,
+                Direction8::NorthWest
+            ]
+        );
+        for dir in diagonals {
+            assert_eq!(Direction::try_from(dir), Err(dir));
+        }
+        for dir in [Direction::North, Direction::East, Direction::South, Direction::West] {
+            let dir8 = Direction8::from(dir);
+            assert!(!dir8.is_diagonal());
+            assert_eq!(dir8.offset(), dir.offset());
+            assert_eq!(Direction::try_from(dir8), Ok(dir));
+        }
+    }
+
+    #[test]
+    fn test_step8_at_edges() {
+        let map = PointMap::new(vec![0; 6], 3, 2);
+
+        let corner = map.neighbors8(Point::new(0, 0));
+        assert_eq!(
+            corner,
+            [
+                None,
+                None,
+                Some(Point::new(1, 0)),
+                Some(Point::new(1, 1)),
+                Some(Point::new(0, 1)),
+                None,
+                None,
+                None
+            ]
+        );
+
+        let far = Point::new(2, 1);
+        assert_eq!(map.step8(far, Direction8::East), None);
+        assert_eq!(map.step8(far, Direction8::SouthWest), None);
+        assert_eq!(map.step8(far, Direction8::NorthWest), Some(Point::new(1, 0)));
+        assert_eq!(map.neighbors8(far).iter().flatten().count(), 3);
+        assert_eq!(map.neighbors8(Point::new(1, 0)).iter().flatten().count(), 5);
     }
 }
@@ -0,0 +1,215 @@
+use std::{error::Error, fmt::Display};
+
+use crate::point::Point;
+
+use super::PointMap;
+
+/// Conversion from a single byte of a text grid into a cell.
+pub trait FromGridByte: Sized {
+    fn from_grid_byte(byte: u8) -> Option<Self>;
+}
+
+impl FromGridByte for u8 {
+    fn from_grid_byte(byte: u8) -> Option<Self> {
+        Some(byte)
+    }
+}
+
+impl FromGridByte for char {
+    fn from_grid_byte(byte: u8) -> Option<Self> {
+        Some(byte as char)
+    }
+}
+
+impl FromGridByte for bool {
+    fn from_grid_byte(byte: u8) -> Option<Self> {
+        match byte {
+            b'#' => Some(true),
+            b'.' => Some(false),
+            _ => None,
+        }
+    }
+}
+
+/// An error which can be returned when parsing a [`PointMap`] from text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GridParseError {
+    Empty,
+    /// A row whose width differs from the first. `line` is 1-based, as in
+    /// [`crate::parse::cursor::ParseError`].
+    RaggedRow { line: usize, expected: usize, found: usize },
+    InvalidByte { byte: u8, point: Point },
+    MissingMarker(u8),
+    DuplicateMarker { byte: u8, first: Point, second: Point },
+}
+
+impl Error for GridParseError {}
+
+impl Display for GridParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GridParseError::Empty => write!(f, "grid is empty"),
+            GridParseError::RaggedRow { line, expected, found } => write!(
+                f,
+                "line {} has {} cells, expected {}",
+                line, found, expected
+            ),
+            GridParseError::InvalidByte { byte, point } => {
+                write!(f, "invalid character {:?} at {}", *byte as char, point)
+            }
+            GridParseError::MissingMarker(byte) => {
+                write!(f, "marker {:?} not found in grid", *byte as char)
+            }
+            GridParseError::DuplicateMarker { byte, first, second } => write!(
+                f,
+                "marker {:?} found at both {} and {}",
+                *byte as char, first, second
+            ),
+        }
+    }
+}
+
+impl<T> PointMap<T> {
+    /// Parses a rectangular grid of lines, converting each byte with [`FromGridByte`].
+    pub fn parse(input: &str) -> Result<Self, GridParseError>
+    where
+        T: FromGridByte,
+    {
+        Self::parse_with(input, T::from_grid_byte)
+    }
+
+    /// Parses a rectangular grid of lines, converting each byte with `cell`.
+    /// Bytes for which `cell` returns `None` are reported as invalid.
+    pub fn parse_with<F>(input: &str, cell: F) -> Result<Self, GridParseError>
+    where
+        F: FnMut(u8) -> Option<T>,
+    {
+        Self::parse_with_markers(input, [], cell).map(|(map, [])| map)
+    }
+
+    /// Like [`PointMap::parse_with`], but also returns the position of each of
+    /// the `markers` bytes, which must each occur exactly once in the grid.
+    /// Marker bytes are still passed to `cell` to decide what lies beneath them.
+    pub fn parse_with_markers<F, const N: usize>(
+        input: &str,
+        markers: [u8; N],
+        mut cell: F,
+    ) -> Result<(Self, [Point; N]), GridParseError>
+    where
+        F: FnMut(u8) -> Option<T>,
+    {
+        let mut vec = Vec::new();
+        let mut found: [Option<Point>; N] = [None; N];
+        let mut width = 0;
+        let mut height = 0;
+
+        for (y, line) in input.trim_end_matches(['\r', '\n']).lines().enumerate() {
+            let line = line.as_bytes();
+            if y == 0 {
+                width = line.len();
+            } else if line.len() != width {
+                return Err(GridParseError::RaggedRow {
+                    line: y + 1,
+                    expected: width,
+                    found: line.len(),
+                });
+            }
+
+            for (x, &byte) in line.iter().enumerate() {
+                let point = Point::new(x, y);
+                if let Some(i) = markers.iter().position(|&m| m == byte) {
+                    if let Some(first) = found[i] {
+                        return Err(GridParseError::DuplicateMarker {
+                            byte,
+                            first,
+                            second: point,
+                        });
+                    }
+                    found[i] = Some(point);
+                }
+
+                match cell(byte) {
+                    Some(value) => vec.push(value),
+                    None => return Err(GridParseError::InvalidByte { byte, point }),
+                }
+            }
+            height += 1;
+        }
+
+        if width == 0 {
+            return Err(GridParseError::Empty);
+        }
+
+        let mut positions = [Point::new(0, 0); N];
+        for (i, marker) in markers.iter().enumerate() {
+            positions[i] = found[i].ok_or(GridParseError::MissingMarker(*marker))?;
+        }
+
+        Ok((Self::new(vec, width, height), positions))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cell(byte: u8) -> Option<u8> {
+        matches!(byte, b'.' | b'#' | b'S' | b'E').then_some(byte)
+    }
+
+    #[test]
+    fn test_parse() {
+        let map = PointMap::<bool>::parse("#..\n.#.\n").unwrap();
+        assert_eq!((map.width, map.height), (3, 2));
+        assert_eq!(map.vec, [true, false, false, false, true, false]);
+    }
+
+    #[test]
+    fn test_crlf() {
+        let map = PointMap::<u8>::parse("ab\r\ncd\r\n\r\n").unwrap();
+        assert_eq!((map.width, map.height), (2, 2));
+        assert_eq!(map.vec, b"abcd");
+    }
+
+    #[test]
+    fn test_markers() {
+        let (map, [start, end]) =
+            PointMap::parse_with_markers("S.#\n..E", [b'S', b'E'], cell).unwrap();
+        assert_eq!(start, Point::new(0, 0));
+        assert_eq!(end, Point::new(2, 1));
+        assert_eq!(map[end], b'E');
+    }
+
+    #[test]
+    fn test_errors() {
+        assert_eq!(PointMap::<u8>::parse("").err(), Some(GridParseError::Empty));
+        assert_eq!(PointMap::<u8>::parse("\n\n").err(), Some(GridParseError::Empty));
+        assert_eq!(
+            PointMap::<u8>::parse("abc\nab\nabc").err(),
+            Some(GridParseError::RaggedRow {
+                line: 2,
+                expected: 3,
+                found: 2
+            })
+        );
+        assert_eq!(
+            PointMap::<bool>::parse("#.\n.x").err(),
+            Some(GridParseError::InvalidByte {
+                byte: b'x',
+                point: Point::new(1, 1)
+            })
+        );
+        assert_eq!(
+            PointMap::parse_with_markers("S..\n...", [b'S', b'E'], cell).err(),
+            Some(GridParseError::MissingMarker(b'E'))
+        );
+        assert_eq!(
+            PointMap::parse_with_markers("S.E\n.S.", [b'S', b'E'], cell).err(),
+            Some(GridParseError::DuplicateMarker {
+                byte: b'S',
+                first: Point::new(0, 0),
+                second: Point::new(1, 1)
+            })
+        );
+    }
+}
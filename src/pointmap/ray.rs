@@ -0,0 +1,174 @@
+use crate::point::{ipoint::IPoint, Point};
+
+use super::{Direction8, Grid};
+
+/// Iterator over the cells of a [`Grid`] along a straight line, starting at
+/// (and including) the start point and stopping at the edge of the grid.
+pub struct Ray<'a, G> {
+    map: &'a G,
+    next: Option<Point>,
+    dir: Direction8,
+}
+
+impl<'a, G: Grid> Iterator for Ray<'a, G> {
+    type Item = (Point, &'a G::Cell);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let point = self.next?;
+        self.next = self.map.step8(point, self.dir);
+        Some((point, self.map.at(point)))
+    }
+}
+
+/// Straight-line searches over any [`Grid`], such as a [`PointMap`](super::PointMap)
+/// or one of its windows.
+pub trait Rays: Grid + Sized {
+    fn ray(&self, start: Point, dir: Direction8) -> Ray<'_, Self> {
+        let next = if self.contains(start) { Some(start) } else { None };
+        Ray { map: self, next, dir }
+    }
+
+    /// Whether `sequence` can be read starting at `start` and walking towards `dir`.
+    fn matches_sequence(&self, start: Point, dir: Direction8, sequence: &[Self::Cell]) -> bool
+    where
+        Self::Cell: PartialEq,
+    {
+        let mut ray = self.ray(start, dir);
+        sequence
+            .iter()
+            .all(|expected| ray.next().is_some_and(|(_, cell)| cell == expected))
+    }
+
+    /// Every start point and direction from which `sequence` can be read.
+    ///
+    /// Each matching pair is reported separately, so a one-element sequence
+    /// yields all eight directions for every matching cell, and a palindrome is
+    /// found once from each of its ends.
+    fn find_sequences(&self, sequence: &[Self::Cell]) -> Vec<(Point, Direction8)>
+    where
+        Self::Cell: PartialEq,
+    {
+        let Some(first) = sequence.first() else {
+            return Vec::new();
+        };
+
+        self.points()
+            .filter(|p| self.at(*p) == first)
+            .flat_map(|p| Direction8::ALL.into_iter().map(move |dir| (p, dir)))
+            .filter(|(p, dir)| self.matches_sequence(*p, *dir, sequence))
+            .collect()
+    }
+
+    /// Every point at which `sequence` crosses itself diagonally in an X shape,
+    /// read in either direction along both diagonals. The sequence must have an
+    /// odd length, and the returned points are the shared centre cells.
+    fn find_crosses(&self, sequence: &[Self::Cell]) -> Vec<Point>
+    where
+        Self::Cell: PartialEq,
+    {
+        if sequence.len().is_multiple_of(2) {
+            return Vec::new();
+        }
+        let half = (sequence.len() / 2) as isize;
+
+        let diagonal = |centre: Point, dir: Direction8| {
+            [dir, dir.reverse()].into_iter().any(|dir| {
+                let start = IPoint::from_point(centre) - dir.offset() * half;
+                start
+                    .to_point_in(self.width(), self.height())
+                    .is_some_and(|start| self.matches_sequence(start, dir, sequence))
+            })
+        };
+
+        self.points()
+            .filter(|p| self.at(*p) == &sequence[sequence.len() / 2])
+            .filter(|p| {
+                diagonal(*p, Direction8::SouthEast) && diagonal(*p, Direction8::SouthWest)
+            })
+            .collect()
+    }
+}
+
+impl<G: Grid> Rays for G {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pointmap::PointMap;
+
+    fn map() -> PointMap<u8> {
+        PointMap::parse_with("ABC\nDEF\nGHI", Some).unwrap()
+    }
+
+    #[test]
+    fn test_ray() {
+        let map = map();
+        let cells: Vec<_> = map
+            .ray(Point::new(0, 0), Direction8::SouthEast)
+            .map(|(p, cell)| (p, *cell))
+            .collect();
+        assert_eq!(
+            cells,
+            [
+                (Point::new(0, 0), b'A'),
+                (Point::new(1, 1), b'E'),
+                (Point::new(2, 2), b'I')
+            ]
+        );
+        assert_eq!(map.ray(Point::new(1, 0), Direction8::North).count(), 1);
+        assert_eq!(map.ray(Point::new(3, 0), Direction8::West).count(), 0);
+    }
+
+    #[test]
+    fn test_matches_sequence() {
+        let map = map();
+        assert!(map.matches_sequence(Point::new(2, 0), Direction8::South, b"CFI"));
+        assert!(map.matches_sequence(Point::new(2, 0), Direction8::South, b"CF"));
+        assert!(!map.matches_sequence(Point::new(2, 0), Direction8::South, b"CFIX"));
+        assert!(!map.matches_sequence(Point::new(2, 0), Direction8::West, b"CFI"));
+        assert!(map.matches_sequence(Point::new(2, 0), Direction8::West, b""));
+    }
+
+    #[test]
+    fn test_find_sequences() {
+        let map = map();
+        assert_eq!(map.find_sequences(b"BEH"), [(Point::new(1, 0), Direction8::South)]);
+        assert_eq!(map.find_sequences(b"E").len(), 8);
+        assert!(map.find_sequences(b"").is_empty());
+
+        let palindrome = PointMap::parse_with("ABA", Some).unwrap();
+        assert_eq!(
+            palindrome.find_sequences(b"ABA"),
+            [
+                (Point::new(0, 0), Direction8::East),
+                (Point::new(2, 0), Direction8::West)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_find_crosses() {
+        let map = PointMap::parse_with("M.S\n.A.\nM.S\n", Some).unwrap();
+        assert_eq!(map.find_crosses(b"MAS"), [Point::new(1, 1)]);
+        assert_eq!(map.find_crosses(b"A"), [Point::new(1, 1)]);
+        assert!(map.find_crosses(b"MA").is_empty());
+        assert!(map.find_crosses(b"MASA").is_empty());
+    }
+
+    #[test]
+    fn test_window_search() {
+        let map = PointMap::parse_with("XMAS.\n..S.S\n...A.\n..M.M\n", Some).unwrap();
+        let window = map.window(Point::new(2, 1), 3, 3);
+        assert_eq!(
+            window.find_sequences(b"SAM"),
+            [
+                (Point::new(0, 0), Direction8::SouthEast),
+                (Point::new(2, 0), Direction8::SouthWest)
+            ]
+        );
+        assert_eq!(window.find_crosses(b"MAS"), [Point::new(1, 1)]);
+        assert_eq!(window.ray(Point::new(2, 0), Direction8::West).count(), 3);
+        assert!(window.find_sequences(b"XMAS").is_empty());
+        assert_eq!(map.find_sequences(b"XMAS").len(), 1);
+    }
+}
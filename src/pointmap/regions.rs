@@ -0,0 +1,198 @@
+use std::collections::VecDeque;
+
+use crate::{bitgrid::BitGrid, point::Point, rect::Rect};
+
+use super::{Direction, Direction8, PointMap};
+
+/// A connected group of cells, as found by [`PointMap::regions`].
+#[derive(Debug, Clone)]
+pub struct Region {
+    pub label: usize,
+    pub cells: Vec<Point>,
+}
+
+impl Region {
+    pub fn area(&self) -> usize {
+        self.cells.len()
+    }
+
+    /// The smallest rectangle covering every cell of the region.
+    pub fn bounds(&self) -> Rect<usize> {
+        Rect::bounding(self.cells.iter().copied()).expect("a region has at least one cell")
+    }
+}
+
+/// Every region of a map, together with a map of the label of each cell.
+#[derive(Debug, Clone)]
+pub struct Regions {
+    pub labels: PointMap<usize>,
+    pub regions: Vec<Region>,
+}
+
+impl Regions {
+    pub fn iter(&self) -> std::slice::Iter<'_, Region> {
+        self.regions.iter()
+    }
+
+    pub fn label_at(&self, point: Point) -> usize {
+        self.labels[point]
+    }
+
+    fn contains(&self, region: &Region, point: Option<Point>) -> bool {
+        point.is_some_and(|p| self.labels[p] == region.label)
+    }
+
+    /// The number of cell edges separating the region from anything else.
+    pub fn perimeter(&self, region: &Region) -> usize {
+        region
+            .cells
+            .iter()
+            .flat_map(|cell| self.labels.neighbors(*cell))
+            .filter(|n| !self.contains(region, *n))
+            .count()
+    }
+
+    /// The number of straight sides of the region, including those around holes.
+    pub fn sides(&self, region: &Region) -> usize {
+        // A polygon has as many sides as it has corners, and each corner
+        // belongs to exactly one cell.
+        let mut corners = 0;
+        for cell in &region.cells {
+            for dir in Direction::ALL {
+                let a = self.contains(region, self.labels.step(*cell, dir));
+                let b = self.contains(region, self.labels.step(*cell, dir.rotate_clockwise()));
+                let diagonal = self.contains(
+                    region,
+                    self.labels.step8(*cell, Direction8::from(dir).rotate_clockwise_45()),
+                );
+                if (!a && !b) || (a && b && !diagonal) {
+                    corners += 1;
+                }
+            }
+        }
+        corners
+    }
+
+    /// The number of separate areas of other cells completely enclosed by the region.
+    pub fn holes(&self, region: &Region) -> usize {
+        let bounds = region.bounds();
+        let (min, max) = (bounds.min, bounds.max - Point::new(1, 1));
+        let on_edge = |p: Point| p.x == min.x || p.x == max.x || p.y == min.y || p.y == max.y;
+
+        // Only cells within the bounding box are ever flooded, so `seen` is
+        // indexed relative to its corner.
+        let mut seen = BitGrid::new(bounds.width(), bounds.height());
+        let mut holes = 0;
+
+        for start in bounds.points() {
+            if seen.contains(start - min) || self.labels[start] == region.label {
+                continue;
+            }
+
+            // Diagonal gaps between cells of a region do not let anything
+            // out, so the surrounding area is flooded with all 8 neighbours.
+            let mut enclosed = true;
+            let mut queue = VecDeque::from([start]);
+            seen.insert(start - min);
+            while let Some(p) = queue.pop_front() {
+                enclosed &= !on_edge(p);
+                for n in self.labels.neighbors8(p).into_iter().flatten() {
+                    if bounds.contains(n)
+                        && self.labels[n] != region.label
+                        && seen.insert(n - min)
+                    {
+                        queue.push_back(n);
+                    }
+                }
+            }
+
+            if enclosed {
+                holes += 1;
+            }
+        }
+
+        holes
+    }
+}
+
+impl<T> PointMap<T> {
+    /// Labels every 4-connected region of the map, where two neighbouring
+    /// cells belong to the same region when `same_region` holds for them.
+    pub fn regions(&self, same_region: impl Fn(&T, &T) -> bool) -> Regions {
+        const UNLABELLED: usize = usize::MAX;
+
+        let mut labels = PointMap::new(vec![UNLABELLED; self.vec.len()], self.width, self.height);
+        let mut regions = Vec::new();
+
+        for start in self.points() {
+            if labels[start] != UNLABELLED {
+                continue;
+            }
+
+            let label = regions.len();
+            let mut region = Region { label, cells: Vec::new() };
+            let mut queue = VecDeque::from([start]);
+            labels[start] = label;
+
+            while let Some(point) = queue.pop_front() {
+                region.cells.push(point);
+
+                for n in self.neighbors(point).into_iter().flatten() {
+                    if labels[n] == UNLABELLED && same_region(self.at(point), self.at(n)) {
+                        labels[n] = label;
+                        queue.push_back(n);
+                    }
+                }
+            }
+
+            regions.push(region);
+        }
+
+        Regions { labels, regions }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAP: &str = "\
+.......
+.AAAAA.
+.A.A.A.
+.AAAAA.
+.......
+BB.CCC.
+";
+
+    fn region_of(regions: &Regions, point: Point) -> &Region {
+        &regions.regions[regions.label_at(point)]
+    }
+
+    #[test]
+    fn test_regions() {
+        let map = PointMap::parse_with(MAP, Some).unwrap();
+        let regions = map.regions(|a, b| a == b);
+        assert_eq!(regions.iter().count(), 6);
+
+        let a = region_of(&regions, Point::new(1, 1));
+        assert_eq!(a.area(), 13);
+        assert_eq!(a.bounds(), Rect::new(Point::new(1, 1), Point::new(6, 4)));
+        assert_eq!(regions.perimeter(a), 24);
+        assert_eq!(regions.sides(a), 12);
+
+        let c = region_of(&regions, Point::new(3, 5));
+        assert_eq!(c.bounds(), Rect::new(Point::new(3, 5), Point::new(6, 6)));
+        assert_eq!(regions.perimeter(c), 8);
+        assert_eq!(regions.sides(c), 4);
+    }
+
+    #[test]
+    fn test_holes() {
+        let map = PointMap::parse_with(MAP, Some).unwrap();
+        let regions = map.regions(|a, b| a == b);
+        assert_eq!(regions.holes(region_of(&regions, Point::new(1, 1))), 2);
+        assert_eq!(regions.holes(region_of(&regions, Point::new(0, 0))), 1);
+        assert_eq!(regions.holes(region_of(&regions, Point::new(0, 5))), 0);
+    }
+}
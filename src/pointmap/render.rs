@@ -0,0 +1,231 @@
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    io::Write,
+};
+
+use crate::point::Point;
+
+use super::{Direction, PointMap};
+
+/// Conversion of a cell into the character used to draw it.
+pub trait ToGridChar {
+    fn to_grid_char(&self) -> char;
+}
+
+impl ToGridChar for u8 {
+    fn to_grid_char(&self) -> char {
+        *self as char
+    }
+}
+
+impl ToGridChar for char {
+    fn to_grid_char(&self) -> char {
+        *self
+    }
+}
+
+impl ToGridChar for bool {
+    fn to_grid_char(&self) -> char {
+        if *self {
+            '#'
+        } else {
+            '.'
+        }
+    }
+}
+
+impl ToGridChar for Direction {
+    fn to_grid_char(&self) -> char {
+        match self {
+            Direction::North => '^',
+            Direction::East => '>',
+            Direction::South => 'v',
+            Direction::West => '<',
+        }
+    }
+}
+
+/// A drawing of a [`PointMap`], with optional characters drawn over the top of
+/// some of its cells. Use it through its [`Display`] implementation.
+pub struct Render<'a, T, F> {
+    map: &'a PointMap<T>,
+    cell: F,
+    overlay: HashMap<Point, char>,
+}
+
+impl<'a, T, F: Fn(&T) -> char> Render<'a, T, F> {
+    /// Draws `c` over each of `points`.
+    pub fn highlight(mut self, points: impl IntoIterator<Item = Point>, c: char) -> Self {
+        self.overlay.extend(points.into_iter().map(|p| (p, c)));
+        self
+    }
+
+    /// Draws an arrow on each step of `path` pointing towards the next step.
+    /// Steps which are not orthogonally adjacent to the next are drawn as `*`.
+    pub fn path(mut self, path: &[Point]) -> Self {
+        for step in path.windows(2) {
+            let c = Direction::ALL
+                .into_iter()
+                .find(|dir| self.map.step(step[0], *dir) == Some(step[1]))
+                .map_or('*', |dir| dir.to_grid_char());
+            self.overlay.insert(step[0], c);
+        }
+        self
+    }
+
+    fn char_at(&self, point: Point) -> char {
+        self.overlay
+            .get(&point)
+            .copied()
+            .unwrap_or_else(|| (self.cell)(self.map.at(point)))
+    }
+}
+
+impl<T, F: Fn(&T) -> char> Display for Render<'_, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.map.height {
+            let row = (0..self.map.width)
+                .map(|x| self.char_at(Point::new(x, y)))
+                .collect::<String>();
+            writeln!(f, "{}", row)?;
+        }
+        Ok(())
+    }
+}
+
+/// Two maps of the same size drawn side by side, followed by a third panel
+/// marking every cell that differs between them with `*`.
+pub struct Diff<'a, T, F> {
+    left: &'a PointMap<T>,
+    right: &'a PointMap<T>,
+    cell: F,
+}
+
+impl<T: PartialEq, F> Diff<'_, T, F> {
+    pub fn changed_points(&self) -> Vec<Point> {
+        self.left
+            .points()
+            .filter(|p| self.left.at(*p) != self.right.at(*p))
+            .collect()
+    }
+}
+
+impl<T: PartialEq, F: Fn(&T) -> char> Display for Diff<'_, T, F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for y in 0..self.left.height {
+            let points = (0..self.left.width).map(|x| Point::new(x, y));
+            let left = points.clone().map(|p| (self.cell)(self.left.at(p)));
+            let right = points.clone().map(|p| (self.cell)(self.right.at(p)));
+            let marks = points.map(|p| {
+                if self.left.at(p) != self.right.at(p) {
+                    '*'
+                } else {
+                    ' '
+                }
+            });
+
+            // Only the marks panel is trimmed, as cells may themselves draw as spaces
+            let line = format!("{} | {} |", left.collect::<String>(), right.collect::<String>());
+            let marks = marks.collect::<String>();
+            match marks.trim_end() {
+                "" => writeln!(f, "{}", line)?,
+                marks => writeln!(f, "{} {}", line, marks)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T> PointMap<T> {
+    /// Draws the map using `cell` to pick the character for each cell.
+    pub fn render<F: Fn(&T) -> char>(&self, cell: F) -> Render<'_, T, F> {
+        Render {
+            map: self,
+            cell,
+            overlay: HashMap::new(),
+        }
+    }
+
+    /// Draws this map next to `other`, marking the cells that differ. Both
+    /// maps must be the same size.
+    pub fn diff<'a, F: Fn(&T) -> char>(
+        &'a self,
+        other: &'a PointMap<T>,
+        cell: F,
+    ) -> Diff<'a, T, F> {
+        assert!(
+            self.width == other.width && self.height == other.height,
+            "cannot diff maps of different sizes"
+        );
+        Diff {
+            left: self,
+            right: other,
+            cell,
+        }
+    }
+}
+
+impl<T: ToGridChar> Display for PointMap<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.render(T::to_grid_char).fmt(f)
+    }
+}
+
+/// Writes `value` to stderr when the `AOC_DEBUG` environment variable is set,
+/// keeping debugging output away from the solution output on stdout.
+pub fn debug(value: impl Display) {
+    if std::env::var_os("AOC_DEBUG").is_some() {
+        let _ = writeln!(std::io::stderr(), "{}", value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_display() {
+        let map = PointMap::new(vec![true, false, false, true], 2, 2);
+        assert_eq!(map.to_string(), "#.\n.#\n");
+    }
+
+    #[test]
+    fn test_path_and_highlight() {
+        let map = PointMap::new(vec![b'.'; 9], 3, 3);
+        let path = [
+            Point::new(0, 0),
+            Point::new(1, 0),
+            Point::new(1, 1),
+            Point::new(0, 1),
+            Point::new(0, 0),
+            Point::new(2, 2),
+        ];
+        let render = map
+            .render(|c| *c as char)
+            .highlight([Point::new(2, 0), Point::new(2, 2)], 'O')
+            .path(&path);
+        assert_eq!(render.to_string(), "*vO\n^<.\n..O\n");
+    }
+
+    #[test]
+    fn test_diff() {
+        let left = PointMap::new(vec![b'a', b' ', b'c', b'd'], 2, 2);
+        let right = PointMap::new(vec![b'a', b'b', b'c', b' '], 2, 2);
+        let diff = left.diff(&right, |c| *c as char);
+        assert_eq!(diff.changed_points(), [Point::new(1, 0), Point::new(1, 1)]);
+        assert_eq!(diff.to_string(), "a  | ab |  *\ncd | c  |  *\n");
+
+        let same = left.diff(&left, |c| *c as char);
+        assert!(same.changed_points().is_empty());
+        assert_eq!(same.to_string(), "a  | a  |\ncd | cd |\n");
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot diff maps of different sizes")]
+    fn test_diff_size_mismatch() {
+        let left = PointMap::new(vec![0; 4], 2, 2);
+        let right = PointMap::new(vec![0; 4], 4, 1);
+        left.diff(&right, |_| '.');
+    }
+}
@@ -0,0 +1,301 @@
+use std::{
+    iter::{Skip, StepBy},
+    ops::Index,
+    slice::Iter,
+};
+
+use crate::point::{ipoint::IPoint, Point};
+
+use super::{Grid, GridIndex, PointMap, Points};
+
+impl<T> PointMap<T> {
+    /// Builds a map by calling `cell` for every point, in row order.
+    pub fn from_fn(width: usize, height: usize, mut cell: impl FnMut(Point) -> T) -> Self {
+        let points = Points {
+            width,
+            height,
+            x: 0,
+            y: 0,
+        };
+        Self::new(points.map(&mut cell).collect(), width, height)
+    }
+
+    pub fn column(&self, x: usize) -> StepBy<Skip<Iter<'_, T>>> {
+        self.vec.iter().skip(x).step_by(self.width)
+    }
+
+    /// Swaps rows and columns, mirroring the map along its main diagonal.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |p| {
+            self.at(Point::new(p.y, p.x)).clone()
+        })
+    }
+
+    /// Rotates a quarter turn, so that the left column becomes the top row.
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |p| {
+            self.at(Point::new(p.y, self.height - 1 - p.x)).clone()
+        })
+    }
+
+    /// Rotates a quarter turn, so that the right column becomes the top row.
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.height, self.width, |p| {
+            self.at(Point::new(self.width - 1 - p.y, p.x)).clone()
+        })
+    }
+
+    pub fn rotate_180(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(
+            self.vec.iter().rev().cloned().collect(),
+            self.width,
+            self.height,
+        )
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_horizontal(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.width, self.height, |p| {
+            self.at(Point::new(self.width - 1 - p.x, p.y)).clone()
+        })
+    }
+
+    /// Mirrors top to bottom.
+    pub fn flip_vertical(&self) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_fn(self.width, self.height, |p| {
+            self.at(Point::new(p.x, self.height - 1 - p.y)).clone()
+        })
+    }
+
+    /// The map turned by 0, 90, 180 and 270 degrees clockwise.
+    pub fn rotations(&self) -> [Self; 4]
+    where
+        T: Clone,
+    {
+        let quarter = self.rotate_clockwise();
+        let three_quarters = quarter.rotate_180();
+        [self.clone(), quarter, self.rotate_180(), three_quarters]
+    }
+
+    /// All eight rotations and reflections of the map.
+    pub fn orientations(&self) -> [Self; 8]
+    where
+        T: Clone,
+    {
+        let [a, b, c, d] = self.rotations();
+        let [e, f, g, h] = self.flip_horizontal().rotations();
+        [a, b, c, d, e, f, g, h]
+    }
+
+    /// A borrowed rectangular part of the map, `width` by `height` cells in
+    /// size with its top-left corner at `origin`.
+    pub fn window(&self, origin: Point, width: usize, height: usize) -> Window<'_, T> {
+        assert!(
+            origin.x + width <= self.width && origin.y + height <= self.height,
+            "window does not fit in the map"
+        );
+        Window {
+            map: self,
+            origin,
+            width,
+            height,
+        }
+    }
+}
+
+/// A rectangular view into a [`PointMap`], indexed relative to its own
+/// top-left corner.
+#[derive(Debug, Clone, Copy)]
+pub struct Window<'a, T> {
+    map: &'a PointMap<T>,
+    origin: Point,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl<'a, T> Window<'a, T> {
+    pub fn origin(&self) -> Point {
+        self.origin
+    }
+
+    pub fn row(&self, y: usize) -> &'a [T] {
+        &self.map.row(self.origin.y + y)[self.origin.x..self.origin.x + self.width]
+    }
+
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &'a T> {
+        self.map
+            .column(self.origin.x + x)
+            .skip(self.origin.y)
+            .take(self.height)
+    }
+
+    pub fn at(&self, point: Point) -> &'a T {
+        self.map.at(self.origin + point)
+    }
+
+    pub fn ati(&self, point: IPoint) -> Option<&'a T> {
+        self.get(point)
+    }
+
+    /// The cell at `index`, or `None` if it lies outside the window.
+    pub fn get<I: GridIndex>(&self, index: I) -> Option<&'a T> {
+        let point = index.to_grid_point(self.width, self.height)?;
+        Some(self.at(point))
+    }
+
+    pub fn contains<I: GridIndex>(&self, index: I) -> bool {
+        index.to_grid_point(self.width, self.height).is_some()
+    }
+
+    pub fn is_in_bounds(&self, point: Point) -> bool {
+        self.contains(point)
+    }
+
+    pub fn points(&self) -> Points {
+        Points {
+            height: self.height,
+            width: self.width,
+            x: 0,
+            y: 0,
+        }
+    }
+
+    /// Copies the window out into a map of its own.
+    pub fn to_pointmap(&self) -> PointMap<T>
+    where
+        T: Clone,
+    {
+        PointMap::from_fn(self.width, self.height, |p| self.at(p).clone())
+    }
+}
+
+impl<T> Grid for Window<'_, T> {
+    type Cell = T;
+
+    fn width(&self) -> usize {
+        self.width
+    }
+
+    fn height(&self) -> usize {
+        self.height
+    }
+
+    fn at(&self, point: Point) -> &T {
+        Window::at(self, point)
+    }
+}
+
+impl<T, I: GridIndex> Index<I> for Window<'_, T> {
+    type Output = T;
+
+    fn index(&self, index: I) -> &T {
+        self.get(index)
+            .unwrap_or_else(|| panic!("{} is outside the window", index))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn map() -> PointMap<u8> {
+        PointMap::parse_with("abcd\nefgh\nijkl\n", Some).unwrap()
+    }
+
+    fn rows(map: &PointMap<u8>) -> Vec<&str> {
+        (0..map.height)
+            .map(|y| std::str::from_utf8(map.row(y)).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_rotations() {
+        let map = map();
+        assert_eq!(rows(&map.rotate_clockwise()), ["iea", "jfb", "kgc", "lhd"]);
+        assert_eq!(rows(&map.rotate_counterclockwise()), ["dhl", "cgk", "bfj", "aei"]);
+        assert_eq!(rows(&map.rotate_180()), ["lkji", "hgfe", "dcba"]);
+
+        let [a, b, c, d] = map.rotations();
+        assert_eq!(rows(&a), rows(&map));
+        assert_eq!(rows(&b), rows(&map.rotate_clockwise()));
+        assert_eq!(rows(&c), rows(&map.rotate_180()));
+        assert_eq!(rows(&d), rows(&map.rotate_counterclockwise()));
+        assert_eq!(rows(&d.rotate_clockwise()), rows(&map));
+    }
+
+    #[test]
+    fn test_flips() {
+        let map = map();
+        assert_eq!(rows(&map.flip_horizontal()), ["dcba", "hgfe", "lkji"]);
+        assert_eq!(rows(&map.flip_vertical()), ["ijkl", "efgh", "abcd"]);
+        assert_eq!(rows(&map.transpose()), ["aei", "bfj", "cgk", "dhl"]);
+        assert_eq!(
+            rows(&map.transpose()),
+            rows(&map.rotate_clockwise().flip_horizontal())
+        );
+    }
+
+    #[test]
+    fn test_orientations() {
+        let map = map();
+        let orientations = map.orientations();
+        let mut distinct: Vec<_> = orientations.iter().map(rows).collect();
+        distinct.sort();
+        distinct.dedup();
+        assert_eq!(distinct.len(), 8);
+        assert!(distinct.contains(&rows(&map.transpose())));
+        assert!(distinct.contains(&rows(&map.flip_vertical())));
+    }
+
+    #[test]
+    fn test_window() {
+        let map = map();
+        let window = map.window(Point::new(1, 1), 3, 2);
+        assert_eq!(window.row(0), b"fgh");
+        assert_eq!(window.column(1).copied().collect::<Vec<_>>(), b"gk");
+        assert_eq!(window[Point::new(2, 1)], b'l');
+        assert_eq!(window.ati(IPoint::new(0, 1)), Some(&b'j'));
+        assert_eq!(window.ati(IPoint::new(-1, 0)), None);
+        assert_eq!(window.ati(IPoint::new(3, 0)), None);
+        assert_eq!(window.ati(IPoint::new(0, 2)), None);
+        assert_eq!(window[IPoint::new(1, 0)], b'g');
+        assert_eq!(window.get(Point::new(2, 0)), Some(&b'h'));
+        assert_eq!(window.get(Point::new(3, 0)), None);
+        assert!(window.contains(IPoint::new(2, 1)));
+        assert!(!window.contains(IPoint::new(0, -1)));
+        assert_eq!(window.points().count(), 6);
+        assert_eq!(rows(&window.to_pointmap()), ["fgh", "jkl"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "(3,0) is outside the window")]
+    fn test_window_index_outside() {
+        let map = map();
+        let window = map.window(Point::new(0, 0), 3, 3);
+        let _ = window[Point::new(3, 0)];
+    }
+
+    #[test]
+    #[should_panic(expected = "window does not fit in the map")]
+    fn test_window_outside() {
+        map().window(Point::new(2, 0), 3, 1);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{
+    point::{ipoint::IPoint, Point},
+    rect::Rect,
+};
+
+use super::{Direction, Direction8, PointMap};
+
+/// A view of a [`PointMap`] as a torus, where stepping off one edge comes
+/// back in on the opposite edge.
+#[derive(Debug, Clone, Copy)]
+pub struct Wrapping<'a, T> {
+    map: &'a PointMap<T>,
+}
+
+impl<T> PointMap<T> {
+    pub fn wrapping(&self) -> Wrapping<'_, T> {
+        Wrapping { map: self }
+    }
+
+    /// Wraps any signed point onto the map, as if the map repeated forever.
+    pub fn wrap(&self, point: IPoint) -> Point {
+        let area = Rect::from_size(self.width as isize, self.height as isize);
+        Point::from_ipoint(area.wrap(point))
+    }
+}
+
+impl<'a, T> Wrapping<'a, T> {
+    pub fn map(&self) -> &'a PointMap<T> {
+        self.map
+    }
+
+    pub fn wrap(&self, point: IPoint) -> Point {
+        self.map.wrap(point)
+    }
+
+    pub fn at(&self, point: Point) -> &'a T {
+        self.map.at(point)
+    }
+
+    pub fn ati(&self, point: IPoint) -> &'a T {
+        self.map.at(self.wrap(point))
+    }
+
+    pub fn step(&self, point: Point, dir: Direction) -> Point {
+        self.wrap(IPoint::from_point(point) + dir.offset())
+    }
+
+    pub fn step8(&self, point: Point, dir: Direction8) -> Point {
+        self.wrap(IPoint::from_point(point) + dir.offset())
+    }
+
+    pub fn neighbors(&self, point: Point) -> [Point; 4] {
+        Direction::ALL.map(|dir| self.step(point, dir))
+    }
+
+    pub fn neighbors8(&self, point: Point) -> [Point; 8] {
+        Direction8::ALL.map(|dir| self.step8(point, dir))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wrap() {
+        let map = PointMap::new(vec![0; 12], 4, 3);
+        assert_eq!(map.wrap(IPoint::new(1, 2)), Point::new(1, 2));
+        assert_eq!(map.wrap(IPoint::new(4, 3)), Point::new(0, 0));
+        assert_eq!(map.wrap(IPoint::new(-1, -1)), Point::new(3, 2));
+        assert_eq!(map.wrap(IPoint::new(-4001, -3002)), Point::new(3, 1));
+        assert_eq!(map.wrap(IPoint::new(isize::MIN, isize::MIN)), Point::new(0, 1));
+    }
+
+    #[test]
+    fn test_wrapping_steps() {
+        let map = PointMap::new((0..12).collect(), 4, 3);
+        let wrapping = map.wrapping();
+        let corner = Point::new(0, 0);
+
+        assert_eq!(wrapping.step(corner, Direction::North), Point::new(0, 2));
+        assert_eq!(wrapping.step(corner, Direction::West), Point::new(3, 0));
+        assert_eq!(wrapping.step8(corner, Direction8::NorthWest), Point::new(3, 2));
+        assert_eq!(wrapping.step8(Point::new(3, 2), Direction8::SouthEast), corner);
+        assert_eq!(wrapping.neighbors8(corner).len(), 8);
+        assert_eq!(*wrapping.ati(IPoint::new(-1, 5)), 11);
+    }
+}
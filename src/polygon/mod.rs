@@ -0,0 +1,225 @@
+use std::{error::Error, fmt::Display};
+
+use crate::{
+    math::gcd,
+    point::{ipoint::IPoint, traits::Integer},
+    pointmap::Direction,
+};
+
+/// The error returned when a polygon's measurements do not fit in the
+/// requested integer type.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PolygonOverflow;
+
+impl Error for PolygonOverflow {}
+
+impl Display for PolygonOverflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "polygon is too large for the accumulator type")
+    }
+}
+
+/// A closed polygon on the integer lattice. The last vertex joins back to
+/// the first, so a path that returns to its start need not repeat it.
+///
+/// Measurements are returned in a caller-chosen type such as `u64` or
+/// `i128`, failing with [`PolygonOverflow`] when they do not fit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Polygon {
+    pub vertices: Vec<IPoint>,
+}
+
+impl Polygon {
+    pub fn new(vertices: Vec<IPoint>) -> Self {
+        Polygon { vertices }
+    }
+
+    /// Traces a path from `start`, moving `length` cells in each direction.
+    pub fn from_moves(
+        start: IPoint,
+        moves: impl IntoIterator<Item = (Direction, isize)>,
+    ) -> Result<Self, PolygonOverflow> {
+        let mut vertices = vec![start];
+        let mut current = start;
+        for (dir, length) in moves {
+            current = current
+                .checked_add(dir.offset() * length)
+                .ok_or(PolygonOverflow)?;
+            vertices.push(current);
+        }
+        if vertices.len() > 1 && vertices.last() == Some(&start) {
+            vertices.pop();
+        }
+        Ok(Polygon { vertices })
+    }
+
+    fn edges(&self) -> impl Iterator<Item = (IPoint, IPoint)> + '_ {
+        let next = self.vertices.iter().cycle().skip(1);
+        self.vertices.iter().copied().zip(next.copied())
+    }
+
+    /// Twice the enclosed area, by the shoelace formula. Unlike the area
+    /// itself this is always a whole number, and it does not depend on the
+    /// winding direction.
+    pub fn double_area<A>(&self) -> Result<A, PolygonOverflow>
+    where
+        A: Integer + TryFrom<i128>,
+    {
+        // Each cross product of two `isize` coordinates fits in an `i128`, so
+        // only the running sum can overflow. Summing signed keeps the
+        // intermediate values correct whichever way the polygon winds.
+        let sum = self.edges().try_fold(0i128, |sum, (a, b)| {
+            let cross = a.x as i128 * b.y as i128 - b.x as i128 * a.y as i128;
+            sum.checked_add(cross).ok_or(PolygonOverflow)
+        })?;
+        cast(sum.checked_abs().ok_or(PolygonOverflow)?)
+    }
+
+    /// The enclosed area, rounded down when the polygon has diagonal edges.
+    pub fn area<A>(&self) -> Result<A, PolygonOverflow>
+    where
+        A: Integer + TryFrom<i128>,
+    {
+        Ok(self.double_area::<A>()? / (A::ONE + A::ONE))
+    }
+
+    /// The number of lattice points on the edges, counting an edge that is
+    /// traced more than once each time.
+    pub fn boundary_points<A>(&self) -> Result<A, PolygonOverflow>
+    where
+        A: Integer + TryFrom<i128>,
+    {
+        self.edges().try_fold(A::ZERO, |sum, (a, b)| {
+            let off = b.checked_sub(a).ok_or(PolygonOverflow)?;
+            let points = cast(gcd(off.x, off.y) as i128)?;
+            sum.checked_add(points).ok_or(PolygonOverflow)
+        })
+    }
+
+    /// The number of lattice points strictly inside, by Pick's theorem. A
+    /// polygon with no area, such as a path that doubles back on itself, has
+    /// no interior points.
+    pub fn interior_points<A>(&self) -> Result<A, PolygonOverflow>
+    where
+        A: Integer + TryFrom<i128>,
+    {
+        let two = A::ONE + A::ONE;
+        let boundary = self.boundary_points::<A>()?;
+        let shifted = self
+            .double_area::<A>()?
+            .checked_add(two)
+            .ok_or(PolygonOverflow)?;
+        if shifted <= boundary {
+            return Ok(A::ZERO);
+        }
+        Ok((shifted - boundary) / two)
+    }
+
+    /// The number of lattice points inside or on the edges, which for a
+    /// path of grid cells is every cell it encloses or passes through.
+    pub fn lattice_points<A>(&self) -> Result<A, PolygonOverflow>
+    where
+        A: Integer + TryFrom<i128>,
+    {
+        if self.vertices.is_empty() {
+            return Ok(A::ZERO);
+        }
+        // Pick's theorem rearranged to avoid the interior count, so that a
+        // path doubling back on itself still counts each cell once.
+        let two = A::ONE + A::ONE;
+        self.double_area::<A>()?
+            .checked_add(self.boundary_points()?)
+            .map(|v| v / two)
+            .and_then(|v| v.checked_add(A::ONE))
+            .ok_or(PolygonOverflow)
+    }
+}
+
+fn cast<A: TryFrom<i128>>(value: i128) -> Result<A, PolygonOverflow> {
+    A::try_from(value).map_err(|_| PolygonOverflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn polygon(coords: &[(isize, isize)]) -> Polygon {
+        Polygon::new(coords.iter().map(|&(x, y)| IPoint::new(x, y)).collect())
+    }
+
+    #[test]
+    fn test_area() {
+        let square = polygon(&[(0, 0), (4, 0), (4, 3), (0, 3)]);
+        assert_eq!(square.double_area::<i64>(), Ok(24));
+        assert_eq!(square.area::<u64>(), Ok(12));
+
+        let mut reversed = square.clone();
+        reversed.vertices.reverse();
+        assert_eq!(reversed.area::<u64>(), Ok(12));
+        assert_eq!(reversed.area::<i32>(), Ok(12));
+
+        let negative = polygon(&[(-5, -5), (-5, -2), (-1, -5)]);
+        assert_eq!(negative.double_area::<u32>(), Ok(12));
+        assert_eq!(negative.area::<u8>(), Ok(6));
+
+        let diagonal = polygon(&[(0, 0), (1, 0), (0, 1)]);
+        assert_eq!(diagonal.double_area::<u8>(), Ok(1));
+        assert_eq!(diagonal.area::<u8>(), Ok(0));
+    }
+
+    #[test]
+    fn test_pick() {
+        let square = polygon(&[(0, 0), (4, 0), (4, 3), (0, 3)]);
+        assert_eq!(square.boundary_points::<u32>(), Ok(14));
+        assert_eq!(square.interior_points::<u32>(), Ok(6));
+        assert_eq!(square.lattice_points::<u32>(), Ok(20));
+
+        let triangle = polygon(&[(0, 0), (6, 0), (0, 4)]);
+        assert_eq!(triangle.boundary_points::<i64>(), Ok(12));
+        assert_eq!(triangle.interior_points::<i64>(), Ok(7));
+        assert_eq!(triangle.lattice_points::<i64>(), Ok(19));
+    }
+
+    #[test]
+    fn test_degenerate() {
+        let line = polygon(&[(0, 0), (3, 0)]);
+        assert_eq!(line.double_area::<u8>(), Ok(0));
+        assert_eq!(line.boundary_points::<u8>(), Ok(6));
+        assert_eq!(line.interior_points::<u8>(), Ok(0));
+        assert_eq!(line.interior_points::<i8>(), Ok(0));
+        assert_eq!(line.lattice_points::<u8>(), Ok(4));
+
+        let point = polygon(&[(2, 2)]);
+        assert_eq!(point.lattice_points::<u8>(), Ok(1));
+        assert_eq!(polygon(&[]).lattice_points::<u8>(), Ok(0));
+    }
+
+    #[test]
+    fn test_overflow() {
+        let big = polygon(&[(0, 0), (1000, 0), (1000, 1000), (0, 1000)]);
+        assert_eq!(big.area::<u16>(), Err(PolygonOverflow));
+        assert_eq!(big.area::<u32>(), Ok(1_000_000));
+
+        let huge = polygon(&[(isize::MIN, 0), (isize::MAX, 0), (0, isize::MAX)]);
+        assert_eq!(huge.double_area::<i64>(), Err(PolygonOverflow));
+        assert!(huge.double_area::<i128>().is_ok());
+    }
+
+    #[test]
+    fn test_from_moves() {
+        use Direction::*;
+
+        let moves = [(East, 6), (South, 5), (West, 2), (South, 2), (West, 4), (North, 7)];
+        let lagoon = Polygon::from_moves(IPoint::new(0, 0), moves).unwrap();
+        assert_eq!(lagoon.vertices.len(), 6);
+        assert_eq!(lagoon.vertices[2], IPoint::new(6, 5));
+        assert_eq!(lagoon.area::<u64>(), Ok(38));
+        assert_eq!(lagoon.lattice_points::<u64>(), Ok(52));
+
+        let open = Polygon::from_moves(IPoint::new(1, 1), [(East, 2), (South, 1)]).unwrap();
+        assert_eq!(open.vertices.len(), 3);
+
+        let overflow = Polygon::from_moves(IPoint::new(isize::MAX, 0), [(East, 1)]);
+        assert_eq!(overflow, Err(PolygonOverflow));
+    }
+}
@@ -0,0 +1,64 @@
+use core::fmt;
+
+use crate::point::{point3::Point3, traits::Integer};
+
+/// An axis-aligned box covering `min` (included) to `max` (excluded) on
+/// each of the three axes.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub struct Cuboid<T> {
+    pub min: Point3<T>,
+    pub max: Point3<T>,
+}
+
+box_ops!(Cuboid, Point3 { x, y, z }, CuboidPoints);
+
+impl<T: Integer> Cuboid<T> {
+    pub fn volume(&self) -> T {
+        self.measure()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cuboid(min: (i32, i32, i32), max: (i32, i32, i32)) -> Cuboid<i32> {
+        Cuboid::new(Point3::new(min.0, min.1, min.2), Point3::new(max.0, max.1, max.2))
+    }
+
+    #[test]
+    fn test_volume() {
+        let c = cuboid((-1, 0, 2), (2, 2, 6));
+        assert_eq!(c.size(), Point3::new(3, 2, 4));
+        assert_eq!(c.volume(), 24);
+        assert_eq!(c.points().count(), 24);
+        assert_eq!(cuboid((0, 0, 0), (2, 2, 0)).volume(), 0);
+    }
+
+    #[test]
+    fn test_intersection() {
+        let a = cuboid((0, 0, 0), (3, 3, 3));
+        let b = cuboid((1, 2, -1), (5, 4, 1));
+        assert_eq!(a.intersection(&b), Some(cuboid((1, 2, 0), (3, 3, 1))));
+        assert_eq!(a.intersection(&cuboid((0, 0, 3), (3, 3, 4))), None);
+        assert_eq!(a.union(&b), cuboid((0, 0, -1), (5, 4, 3)));
+        assert!(a.contains(Point3::new(2, 2, 2)));
+        assert!(!a.contains(Point3::new(2, 3, 2)));
+    }
+
+    #[test]
+    fn test_points() {
+        let points: Vec<_> = cuboid((0, 0, 0), (2, 1, 2)).points().collect();
+        assert_eq!(
+            points,
+            [
+                Point3::new(0, 0, 0),
+                Point3::new(1, 0, 0),
+                Point3::new(0, 0, 1),
+                Point3::new(1, 0, 1)
+            ]
+        );
+        let bounds = Cuboid::bounding(points);
+        assert_eq!(bounds, Some(cuboid((0, 0, 0), (2, 1, 2))));
+    }
+}
@@ -0,0 +1,292 @@
+use core::fmt;
+
+use crate::point::{
+    traits::{Integer, ModuloPositive},
+    Point2,
+};
+
+/// Implements the operations shared by axis-aligned boxes, which span from
+/// `min` (included) to `max` (excluded) on every axis.
+macro_rules! box_ops {
+    ($box:ident, $point:ident { $($field:ident),+ }, $points:ident) => {
+        impl<T: Integer> $box<T> {
+            pub fn new(min: $point<T>, max: $point<T>) -> Self {
+                $box { min, max }
+            }
+
+            /// The extent along each axis, zero for an empty box.
+            pub fn size(&self) -> $point<T> {
+                $point {
+                    $($field: (self.max.$field - self.min.$field).max(T::ZERO)),+
+                }
+            }
+
+            pub fn is_empty(&self) -> bool {
+                false $(|| self.min.$field >= self.max.$field)+
+            }
+
+            pub fn contains(&self, point: $point<T>) -> bool {
+                true $(&& self.min.$field <= point.$field && point.$field < self.max.$field)+
+            }
+
+            /// Whether `other` lies entirely within this box.
+            pub fn contains_box(&self, other: &Self) -> bool {
+                other.is_empty()
+                    || (true $(&& self.min.$field <= other.min.$field
+                        && other.max.$field <= self.max.$field)+)
+            }
+
+            /// The overlap of both boxes, or `None` if they do not overlap.
+            pub fn intersection(&self, other: &Self) -> Option<Self> {
+                let overlap = $box {
+                    min: $point { $($field: self.min.$field.max(other.min.$field)),+ },
+                    max: $point { $($field: self.max.$field.min(other.max.$field)),+ },
+                };
+                (!overlap.is_empty()).then_some(overlap)
+            }
+
+            /// The smallest box covering both.
+            pub fn union(&self, other: &Self) -> Self {
+                if self.is_empty() {
+                    return *other;
+                }
+                if other.is_empty() {
+                    return *self;
+                }
+                $box {
+                    min: $point { $($field: self.min.$field.min(other.min.$field)),+ },
+                    max: $point { $($field: self.max.$field.max(other.max.$field)),+ },
+                }
+            }
+
+            /// The nearest point inside the box.
+            ///
+            /// # Panics
+            ///
+            /// If the box is empty.
+            pub fn clamp(&self, point: $point<T>) -> $point<T> {
+                assert!(!self.is_empty(), "cannot clamp into an empty box");
+                $point {
+                    $($field: point.$field.clamp(self.min.$field, self.max.$field - T::ONE)),+
+                }
+            }
+
+            /// The smallest box covering every point, or `None` if there are none.
+            pub fn bounding(points: impl IntoIterator<Item = $point<T>>) -> Option<Self> {
+                points.into_iter().fold(None, |bounds: Option<Self>, point| {
+                    let cell = $box {
+                        min: point,
+                        max: $point { $($field: point.$field + T::ONE),+ },
+                    };
+                    Some(bounds.map_or(cell, |b| b.union(&cell)))
+                })
+            }
+
+            fn measure(&self) -> T {
+                let size = self.size();
+                T::ONE $(* size.$field)+
+            }
+
+            /// Every point in the box, with `x` changing fastest.
+            pub fn points(&self) -> $points<T> {
+                $points {
+                    bounds: *self,
+                    next: (!self.is_empty()).then_some(self.min),
+                }
+            }
+        }
+
+        impl<T: fmt::Display> fmt::Display for $box<T> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "{}..{}", self.min, self.max)
+            }
+        }
+
+        pub struct $points<T> {
+            bounds: $box<T>,
+            next: Option<$point<T>>,
+        }
+
+        impl<T: Integer> Iterator for $points<T> {
+            type Item = $point<T>;
+
+            fn next(&mut self) -> Option<Self::Item> {
+                let current = self.next?;
+                let mut next = current;
+                self.next = 'advance: {
+                    $(
+                        next.$field = next.$field + T::ONE;
+                        if next.$field < self.bounds.max.$field {
+                            break 'advance Some(next);
+                        }
+                        next.$field = self.bounds.min.$field;
+                    )+
+                    None
+                };
+                Some(current)
+            }
+        }
+    };
+}
+
+pub mod cuboid;
+
+/// An axis-aligned rectangle covering `min.x..max.x` by `min.y..max.y`.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug, Hash)]
+pub struct Rect<T> {
+    pub min: Point2<T>,
+    pub max: Point2<T>,
+}
+
+box_ops!(Rect, Point2 { x, y }, RectPoints);
+
+impl<T: Integer> Rect<T> {
+    /// The rectangle from the origin to `(width, height)`.
+    pub fn from_size(width: T, height: T) -> Self {
+        Rect::new(Point2::default(), Point2::new(width, height))
+    }
+
+    pub fn width(&self) -> T {
+        self.size().x
+    }
+
+    pub fn height(&self) -> T {
+        self.size().y
+    }
+
+    pub fn area(&self) -> T {
+        self.measure()
+    }
+
+    /// Wraps any point into the rectangle, as if copies of it tiled the plane.
+    pub fn wrap(&self, point: Point2<T>) -> Point2<T> {
+        assert!(!self.is_empty(), "cannot wrap into an empty rectangle");
+        (point - self.min).modulo_positive(self.size()) + self.min
+    }
+
+    /// The four quadrants in reading order: top-left, top-right, bottom-left
+    /// and bottom-right. On an odd-sized side the middle row or column
+    /// belongs to no quadrant.
+    pub fn quadrants(&self) -> [Self; 4] {
+        let two = T::ONE + T::ONE;
+        let half = self.size() / two;
+        let (left, right) = (self.min.x..self.min.x + half.x, self.max.x - half.x..self.max.x);
+        let (top, bottom) = (self.min.y..self.min.y + half.y, self.max.y - half.y..self.max.y);
+        [(&left, &top), (&right, &top), (&left, &bottom), (&right, &bottom)].map(|(xs, ys)| {
+            Rect::new(Point2::new(xs.start, ys.start), Point2::new(xs.end, ys.end))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(min: (isize, isize), max: (isize, isize)) -> Rect<isize> {
+        Rect::new(Point2::new(min.0, min.1), Point2::new(max.0, max.1))
+    }
+
+    #[test]
+    fn test_size() {
+        let r = rect((-2, 1), (3, 4));
+        assert_eq!((r.width(), r.height(), r.area()), (5, 3, 15));
+        assert!(r.contains(Point2::new(-2, 3)));
+        assert!(!r.contains(Point2::new(3, 3)));
+
+        let empty = rect((2, 2), (1, 5));
+        assert!(empty.is_empty());
+        assert_eq!((empty.width(), empty.area()), (0, 0));
+        assert!(!empty.contains(Point2::new(1, 3)));
+    }
+
+    #[test]
+    fn test_intersection_union() {
+        let a = rect((0, 0), (4, 4));
+        let b = rect((2, -1), (6, 3));
+        assert_eq!(a.intersection(&b), Some(rect((2, 0), (4, 3))));
+        assert_eq!(a.union(&b), rect((0, -1), (6, 4)));
+
+        // Touching edges do not overlap
+        assert_eq!(a.intersection(&rect((4, 0), (5, 4))), None);
+
+        let empty = rect((10, 10), (10, 12));
+        assert_eq!(a.union(&empty), a);
+        assert_eq!(empty.union(&a), a);
+        assert!(a.contains_box(&empty));
+        assert!(a.contains_box(&rect((1, 1), (4, 2))));
+        assert!(!a.contains_box(&b));
+    }
+
+    #[test]
+    fn test_clamp_and_bounding() {
+        let r = rect((0, 0), (4, 3));
+        assert_eq!(r.clamp(Point2::new(-5, 1)), Point2::new(0, 1));
+        assert_eq!(r.clamp(Point2::new(9, 9)), Point2::new(3, 2));
+
+        let points = [Point2::new(3, -1), Point2::new(-2, 4), Point2::new(0, 0)];
+        assert_eq!(Rect::bounding(points), Some(rect((-2, -1), (4, 5))));
+        assert_eq!(Rect::<isize>::bounding([]), None);
+    }
+
+    #[test]
+    fn test_wrap() {
+        let r = rect((-2, 1), (3, 4));
+        assert_eq!(r.wrap(Point2::new(0, 2)), Point2::new(0, 2));
+        assert_eq!(r.wrap(Point2::new(3, 4)), Point2::new(-2, 1));
+        assert_eq!(r.wrap(Point2::new(-3, 0)), Point2::new(2, 3));
+        assert_eq!(r.wrap(Point2::new(13, -11)), Point2::new(-2, 1));
+
+        let field = Rect::from_size(4, 3);
+        assert_eq!(field.wrap(Point2::new(isize::MIN, isize::MIN)), Point2::new(0, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot wrap into an empty rectangle")]
+    fn test_wrap_empty() {
+        rect((0, 0), (0, 3)).wrap(Point2::new(1, 1));
+    }
+
+    #[test]
+    #[should_panic(expected = "cannot clamp into an empty box")]
+    fn test_clamp_empty() {
+        rect((0, 0), (0, 3)).clamp(Point2::new(0, 0));
+    }
+
+    #[test]
+    fn test_points() {
+        let points: Vec<_> = rect((1, -1), (3, 1)).points().collect();
+        assert_eq!(
+            points,
+            [
+                Point2::new(1, -1),
+                Point2::new(2, -1),
+                Point2::new(1, 0),
+                Point2::new(2, 0)
+            ]
+        );
+        assert_eq!(rect((1, 1), (1, 3)).points().count(), 0);
+    }
+
+    #[test]
+    fn test_quadrants() {
+        let [tl, tr, bl, br] = Rect::<usize>::from_size(4, 6).quadrants();
+        assert_eq!(tl, Rect::new(Point2::new(0, 0), Point2::new(2, 3)));
+        assert_eq!(tr, Rect::new(Point2::new(2, 0), Point2::new(4, 3)));
+        assert_eq!(bl, Rect::new(Point2::new(0, 3), Point2::new(2, 6)));
+        assert_eq!(br, Rect::new(Point2::new(2, 3), Point2::new(4, 6)));
+
+        // The middle column and row of an odd-sized rectangle are left out
+        let quadrants = rect((1, 1), (6, 4)).quadrants();
+        assert_eq!(
+            quadrants,
+            [
+                rect((1, 1), (3, 2)),
+                rect((4, 1), (6, 2)),
+                rect((1, 3), (3, 4)),
+                rect((4, 3), (6, 4))
+            ]
+        );
+        assert!(quadrants.iter().all(|q| !q.contains(Point2::new(3, 1))));
+        assert!(quadrants.iter().all(|q| !q.contains(Point2::new(1, 2))));
+    }
+}
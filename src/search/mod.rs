@@ -0,0 +1,539 @@
+use std::{
+    cmp::Reverse,
+    collections::{BinaryHeap, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
+
+use crate::{
+    bitgrid::BitGrid,
+    point::Point,
+    pointmap::{Direction, PointMap},
+};
+
+/// A node of a search space that sits on a grid cell. Plain points are the
+/// simplest state, while `(Point, Direction)` allows costs that depend on
+/// which way we are facing.
+pub trait SearchState: Copy + Eq + Hash + Ord {
+    fn point(&self) -> Point;
+}
+
+impl SearchState for Point {
+    fn point(&self) -> Point {
+        *self
+    }
+}
+
+impl SearchState for (Point, Direction) {
+    fn point(&self) -> Point {
+        self.0
+    }
+}
+
+/// A store of the states a search has already seen, such as a [`HashSet`] or,
+/// for grid states, a compact [`BitGrid`]. The breadth-first searches use it
+/// for the states already queued, while the weighted searches take it as
+/// `settled`, holding the states whose distance is final.
+pub trait Visited<S> {
+    /// Marks `state` as seen, returning whether it had not been seen before.
+    fn visit(&mut self, state: S) -> bool;
+    fn is_visited(&self, state: &S) -> bool;
+}
+
+impl<S: Eq + Hash> Visited<S> for HashSet<S> {
+    fn visit(&mut self, state: S) -> bool {
+        self.insert(state)
+    }
+
+    fn is_visited(&self, state: &S) -> bool {
+        self.contains(state)
+    }
+}
+
+impl Visited<Point> for BitGrid {
+    fn visit(&mut self, state: Point) -> bool {
+        self.insert(state)
+    }
+
+    fn is_visited(&self, state: &Point) -> bool {
+        self.contains(*state)
+    }
+}
+
+/// One grid per direction, for states that track which way they are facing.
+impl Visited<(Point, Direction)> for [BitGrid; 4] {
+    fn visit(&mut self, (point, dir): (Point, Direction)) -> bool {
+        self[dir as usize].insert(point)
+    }
+
+    fn is_visited(&self, (point, dir): &(Point, Direction)) -> bool {
+        self[*dir as usize].contains(*point)
+    }
+}
+
+/// The distances and predecessors discovered by a search.
+#[derive(Debug, Clone)]
+pub struct SearchResult<S> {
+    pub distances: HashMap<S, u64>,
+    pub predecessors: HashMap<S, S>,
+    /// The first goal state reached, if any.
+    pub goal: Option<S>,
+}
+
+impl<S: SearchState> SearchResult<S> {
+    fn new() -> Self {
+        Self {
+            distances: HashMap::new(),
+            predecessors: HashMap::new(),
+            goal: None,
+        }
+    }
+
+    pub fn distance(&self, state: &S) -> Option<u64> {
+        self.distances.get(state).copied()
+    }
+
+    /// The distance to the goal state, if one was reached.
+    pub fn goal_distance(&self) -> Option<u64> {
+        self.goal.and_then(|goal| self.distance(&goal))
+    }
+
+    /// The shortest distance to any state on `point`.
+    pub fn distance_to_point(&self, point: Point) -> Option<u64> {
+        self.distances
+            .iter()
+            .filter(|(state, _)| state.point() == point)
+            .map(|(_, dist)| *dist)
+            .min()
+    }
+
+    /// The states from a start state up to and including `state`.
+    pub fn path_to(&self, state: &S) -> Option<Vec<S>> {
+        if !self.distances.contains_key(state) {
+            return None;
+        }
+
+        let mut path = vec![*state];
+        let mut current = *state;
+        while let Some(prev) = self.predecessors.get(&current) {
+            path.push(*prev);
+            current = *prev;
+        }
+        path.reverse();
+
+        Some(path)
+    }
+
+    /// The states from a start state to the goal state.
+    pub fn path(&self) -> Option<Vec<S>> {
+        self.goal.and_then(|goal| self.path_to(&goal))
+    }
+}
+
+/// The distances discovered by [`dijkstra_all`], along with every predecessor
+/// that reaches each state at its optimal distance.
+#[derive(Debug, Clone)]
+pub struct ShortestPaths<S> {
+    pub distances: HashMap<S, u64>,
+    pub predecessors: HashMap<S, Vec<S>>,
+    /// Every goal state reached at the optimal distance.
+    pub goals: Vec<S>,
+}
+
+impl<S: SearchState> ShortestPaths<S> {
+    pub fn distance(&self, state: &S) -> Option<u64> {
+        self.distances.get(state).copied()
+    }
+
+    /// The optimal distance to the goal, if one was reached.
+    pub fn goal_distance(&self) -> Option<u64> {
+        self.goals.first().and_then(|goal| self.distance(goal))
+    }
+
+    /// Every state lying on any shortest path from a start state to `ends`.
+    pub fn states_on_paths_to(&self, ends: impl IntoIterator<Item = S>) -> HashSet<S> {
+        let mut seen = HashSet::new();
+        let mut stack = ends
+            .into_iter()
+            .filter(|end| self.distances.contains_key(end))
+            .collect::<Vec<_>>();
+
+        while let Some(state) = stack.pop() {
+            if !seen.insert(state) {
+                continue;
+            }
+            if let Some(prev) = self.predecessors.get(&state) {
+                stack.extend(prev.iter().copied());
+            }
+        }
+
+        seen
+    }
+
+    /// Every state lying on any shortest path from a start state to a goal.
+    pub fn states_on_paths(&self) -> HashSet<S> {
+        self.states_on_paths_to(self.goals.iter().copied())
+    }
+
+    /// Every grid point lying on any shortest path from a start state to a goal.
+    pub fn points_on_paths(&self) -> HashSet<Point> {
+        self.states_on_paths().iter().map(|s| s.point()).collect()
+    }
+}
+
+/// Breadth-first search where every step costs one. Stops as soon as a state
+/// satisfying `is_goal` is reached; pass `|_| false` to explore everything.
+pub fn bfs<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    visited: &mut impl Visited<S>,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S>
+where
+    S: SearchState,
+    I: IntoIterator<Item = S>,
+{
+    let mut result = SearchResult::new();
+    let mut queue = VecDeque::new();
+
+    for start in starts {
+        if visited.visit(start) {
+            result.distances.insert(start, 0);
+            queue.push_back(start);
+        }
+    }
+
+    while let Some(state) = queue.pop_front() {
+        if is_goal(&state) {
+            result.goal = Some(state);
+            break;
+        }
+
+        let dist = result.distances[&state];
+        for next in successors(&state) {
+            if visited.visit(next) {
+                result.distances.insert(next, dist + 1);
+                result.predecessors.insert(next, state);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    result
+}
+
+/// Breadth-first traversal that only tracks which states have been seen,
+/// calling `visit` with each state and its depth in the order they are reached.
+/// This avoids the cost of recording distances and predecessors when only
+/// reachability matters, and lets the caller pick a compact `visited` store.
+pub fn traverse<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    visited: &mut impl Visited<S>,
+    mut visit: impl FnMut(&S, u64),
+) where
+    S: Copy,
+    I: IntoIterator<Item = S>,
+{
+    let mut queue = VecDeque::new();
+    for start in starts {
+        if visited.visit(start) {
+            queue.push_back((start, 0));
+        }
+    }
+
+    while let Some((state, depth)) = queue.pop_front() {
+        visit(&state, depth);
+        for next in successors(&state) {
+            if visited.visit(next) {
+                queue.push_back((next, depth + 1));
+            }
+        }
+    }
+}
+
+/// Dijkstra's algorithm over weighted successors. Stops as soon as a state
+/// satisfying `is_goal` is settled; pass `|_| false` to explore everything.
+pub fn dijkstra<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    successors: impl FnMut(&S) -> I,
+    settled: &mut impl Visited<S>,
+    is_goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S>
+where
+    S: SearchState,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    astar(starts, successors, |_| 0, settled, is_goal)
+}
+
+/// A* search over weighted successors. The `heuristic` must never
+/// overestimate the remaining cost, nor drop by more than the cost of a step,
+/// for the result to be a shortest path.
+pub fn astar<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> u64,
+    settled: &mut impl Visited<S>,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> SearchResult<S>
+where
+    S: SearchState,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    let mut result = SearchResult::new();
+    let mut heap = BinaryHeap::new();
+
+    for start in starts {
+        result.distances.insert(start, 0);
+        heap.push(Reverse((heuristic(&start), 0, start)));
+    }
+
+    while let Some(Reverse((_, dist, state))) = heap.pop() {
+        if !settled.visit(state) {
+            continue;
+        }
+        if is_goal(&state) {
+            result.goal = Some(state);
+            break;
+        }
+
+        for (next, cost) in successors(&state) {
+            let next_dist = dist + cost;
+            let improved = result
+                .distances
+                .get(&next)
+                .is_none_or(|current| next_dist < *current);
+            if improved {
+                result.distances.insert(next, next_dist);
+                result.predecessors.insert(next, state);
+                heap.push(Reverse((next_dist + heuristic(&next), next_dist, next)));
+            }
+        }
+    }
+
+    result
+}
+
+/// Dijkstra's algorithm that keeps every equal-cost predecessor of each state,
+/// so that all shortest paths can be recovered. Once a goal is settled the
+/// search continues only until every goal at the same distance is found.
+pub fn dijkstra_all<S, I>(
+    starts: impl IntoIterator<Item = S>,
+    mut successors: impl FnMut(&S) -> I,
+    settled: &mut impl Visited<S>,
+    mut is_goal: impl FnMut(&S) -> bool,
+) -> ShortestPaths<S>
+where
+    S: SearchState,
+    I: IntoIterator<Item = (S, u64)>,
+{
+    let mut result = ShortestPaths {
+        distances: HashMap::new(),
+        predecessors: HashMap::new(),
+        goals: Vec::new(),
+    };
+    let mut heap = BinaryHeap::new();
+
+    for start in starts {
+        result.distances.insert(start, 0);
+        heap.push(Reverse((0, start)));
+    }
+
+    while let Some(Reverse((dist, state))) = heap.pop() {
+        if !settled.visit(state) {
+            continue;
+        }
+        if result.goal_distance().is_some_and(|best| dist > best) {
+            break;
+        }
+        if is_goal(&state) {
+            result.goals.push(state);
+            continue;
+        }
+
+        for (next, cost) in successors(&state) {
+            let next_dist = dist + cost;
+            match result.distances.get(&next) {
+                Some(current) if next_dist > *current => {}
+                Some(current) if next_dist == *current => {
+                    result.predecessors.entry(next).or_default().push(state);
+                }
+                _ => {
+                    result.distances.insert(next, next_dist);
+                    result.predecessors.insert(next, vec![state]);
+                    heap.push(Reverse((next_dist, next)));
+                }
+            }
+        }
+    }
+
+    result
+}
+
+impl<T> PointMap<T> {
+    /// The four neighbours of `point` that can be stepped to, paired with
+    /// the cost of the step as given by `cost(from, to)`.
+    fn grid_successors<'a>(
+        &'a self,
+        point: Point,
+        cost: &'a impl Fn(&T, &T) -> Option<u64>,
+    ) -> impl Iterator<Item = (Point, u64)> + 'a {
+        let from = self.at(point);
+        self.neighbors(point)
+            .into_iter()
+            .flatten()
+            .filter_map(move |n| cost(from, self.at(n)).map(|c| (n, c)))
+    }
+
+    /// Every cell reachable from `start` over the four neighbours of each
+    /// cell, where a step is allowed when `passable(from, to)` holds.
+    pub fn reachable(&self, start: Point, passable: impl Fn(&T, &T) -> bool) -> BitGrid {
+        let step = |from: &T, to: &T| passable(from, to).then_some(1);
+        let mut visited = BitGrid::for_map(self);
+        traverse(
+            [start],
+            |p| self.grid_successors(*p, &step).map(|(n, _)| n),
+            &mut visited,
+            |_, _| {},
+        );
+        visited
+    }
+
+    /// Breadth-first search over the four neighbours of each cell, where a
+    /// step is allowed when `passable(from, to)` holds.
+    pub fn bfs(
+        &self,
+        start: Point,
+        passable: impl Fn(&T, &T) -> bool,
+        is_goal: impl FnMut(&Point) -> bool,
+    ) -> SearchResult<Point> {
+        let step = |from: &T, to: &T| passable(from, to).then_some(1);
+        bfs(
+            [start],
+            |p| self.grid_successors(*p, &step).map(|(n, _)| n),
+            &mut BitGrid::for_map(self),
+            is_goal,
+        )
+    }
+
+    /// Dijkstra's algorithm over the four neighbours of each cell, where
+    /// `cost(from, to)` returns the cost of a step, or `None` if it is blocked.
+    pub fn dijkstra(
+        &self,
+        start: Point,
+        cost: impl Fn(&T, &T) -> Option<u64>,
+        is_goal: impl FnMut(&Point) -> bool,
+    ) -> SearchResult<Point> {
+        dijkstra(
+            [start],
+            |p| self.grid_successors(*p, &cost),
+            &mut BitGrid::for_map(self),
+            is_goal,
+        )
+    }
+
+    /// A* towards `goal` over the four neighbours of each cell, using the
+    /// manhattan distance as the heuristic. Step costs must be at least one.
+    pub fn astar(
+        &self,
+        start: Point,
+        goal: Point,
+        cost: impl Fn(&T, &T) -> Option<u64>,
+    ) -> SearchResult<Point> {
+        astar(
+            [start],
+            |p| self.grid_successors(*p, &cost),
+            |p| (p.x.abs_diff(goal.x) + p.y.abs_diff(goal.y)) as u64,
+            &mut BitGrid::for_map(self),
+            |p| *p == goal,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MAZE: &str = "S.#.\n.##.\n....\n#..E\n";
+
+    fn open(_: &u8, to: &u8) -> bool {
+        *to != b'#'
+    }
+
+    #[test]
+    fn test_bfs_distances() {
+        let map = PointMap::parse_with(MAZE, Some).unwrap();
+        let result = map.bfs(Point::new(0, 0), open, |_| false);
+
+        assert_eq!(result.goal, None);
+        assert_eq!(result.distance(&Point::new(0, 2)), Some(2));
+        assert_eq!(result.distance(&Point::new(3, 3)), Some(6));
+        assert_eq!(result.distance(&Point::new(3, 0)), Some(7));
+        assert_eq!(result.distance(&Point::new(2, 0)), None);
+        assert_eq!(result.distances.len(), 12);
+    }
+
+    #[test]
+    fn test_bfs_path() {
+        let map = PointMap::parse_with(MAZE, Some).unwrap();
+        let goal = Point::new(3, 3);
+        let result = map.bfs(Point::new(0, 0), open, |p| *p == goal);
+
+        assert_eq!(result.goal, Some(goal));
+        assert_eq!(result.goal_distance(), Some(6));
+        let path = result.path().unwrap();
+        assert_eq!(path.len(), 7);
+        assert_eq!(path[0], Point::new(0, 0));
+        assert_eq!(path[6], goal);
+        for step in path.windows(2) {
+            assert!(map.neighbors(step[0]).contains(&Some(step[1])));
+            assert_ne!(*map.at(step[1]), b'#');
+        }
+        assert_eq!(result.path_to(&Point::new(2, 0)), None);
+    }
+
+    #[test]
+    fn test_astar_agrees_with_dijkstra() {
+        let map = PointMap::parse_with("1163\n1381\n2136\n3694\n", |b| Some((b - b'0') as u64))
+            .unwrap();
+        let (start, goal) = (Point::new(0, 0), Point::new(3, 3));
+        let cost = |_: &u64, to: &u64| Some(*to);
+
+        let dijkstra = map.dijkstra(start, cost, |p| *p == goal);
+        let astar = map.astar(start, goal, cost);
+        assert_eq!(astar.goal_distance(), Some(17));
+        assert_eq!(astar.goal_distance(), dijkstra.goal_distance());
+        assert_eq!(astar.distance_to_point(goal), Some(17));
+
+        let path = astar.path().unwrap();
+        let total: u64 = path[1..].iter().map(|p| map.at(*p)).sum();
+        assert_eq!(total, 17);
+    }
+
+    #[test]
+    fn test_directed_states() {
+        let map = PointMap::new(vec![(); 9], 3, 3);
+        let successors = |&(p, dir): &(Point, Direction)| {
+            let forward = map.step(p, dir).map(|n| ((n, dir), 1));
+            [
+                forward,
+                Some(((p, dir.rotate_clockwise()), 1000)),
+                Some(((p, dir.rotate_counterclockwise()), 1000)),
+            ]
+            .into_iter()
+            .flatten()
+        };
+        let start = (Point::new(0, 0), Direction::East);
+        let goal = Point::new(2, 2);
+
+        let settled = &mut HashSet::new();
+        let result = dijkstra([start], successors, settled, |s| s.point() == goal);
+        assert_eq!(result.goal, Some((goal, Direction::South)));
+        assert_eq!(result.distance_to_point(goal), Some(1004));
+
+        let settled = &mut std::array::from_fn(|_| BitGrid::for_map(&map));
+        let all = dijkstra_all([start], successors, settled, |s| s.point() == goal);
+        assert_eq!(all.goal_distance(), Some(1004));
+        assert_eq!(all.points_on_paths().len(), 5);
+    }
+}
@@ -0,0 +1,231 @@
+use std::collections::{hash_map, HashMap};
+
+use crate::{
+    point::{ipoint::IPoint, Point},
+    pointmap::{Direction, Direction8, PointMap},
+    rect::Rect,
+};
+
+/// A grid that only stores occupied cells, keyed by signed coordinates, so it
+/// can grow in any direction. The bounding box of the occupied cells is kept
+/// up to date as cells are inserted and removed.
+#[derive(Debug, Clone)]
+pub struct SparseMap<T> {
+    cells: HashMap<IPoint, T>,
+    bounds: Rect<isize>,
+}
+
+impl<T> Default for SparseMap<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SparseMap<T> {
+    pub fn new() -> Self {
+        Self {
+            cells: HashMap::new(),
+            bounds: Rect::default(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn get(&self, point: IPoint) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    pub fn get_mut(&mut self, point: IPoint) -> Option<&mut T> {
+        self.cells.get_mut(&point)
+    }
+
+    pub fn contains(&self, point: IPoint) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    pub fn insert(&mut self, point: IPoint, value: T) -> Option<T> {
+        self.insert_bounds(point);
+        self.cells.insert(point, value)
+    }
+
+    pub fn remove(&mut self, point: IPoint) -> Option<T> {
+        let value = self.cells.remove(&point)?;
+
+        // Only a cell on the edge of the bounding box can shrink it
+        let (min, max) = (self.bounds.min, self.bounds.max - IPoint::new(1, 1));
+        if point.x == min.x || point.x == max.x || point.y == min.y || point.y == max.y {
+            self.bounds = self.compute_bounds();
+        }
+
+        Some(value)
+    }
+
+    fn insert_bounds(&mut self, point: IPoint) {
+        self.bounds = self.bounds.union(&Rect::new(point, point + IPoint::new(1, 1)));
+    }
+
+    fn compute_bounds(&self) -> Rect<isize> {
+        Rect::bounding(self.cells.keys().copied()).unwrap_or_default()
+    }
+
+    /// The smallest rectangle covering the occupied cells, empty if there are none.
+    pub fn bounds(&self) -> Rect<isize> {
+        self.bounds
+    }
+
+    pub fn width(&self) -> usize {
+        self.bounds.width() as usize
+    }
+
+    pub fn height(&self) -> usize {
+        self.bounds.height() as usize
+    }
+
+    pub fn iter(&self) -> hash_map::Iter<'_, IPoint, T> {
+        self.cells.iter()
+    }
+
+    pub fn points(&self) -> hash_map::Keys<'_, IPoint, T> {
+        self.cells.keys()
+    }
+
+    pub fn step(&self, point: IPoint, dir: Direction) -> IPoint {
+        point + dir.offset()
+    }
+
+    pub fn step8(&self, point: IPoint, dir: Direction8) -> IPoint {
+        point + dir.offset()
+    }
+
+    pub fn neighbors(&self, point: IPoint) -> [IPoint; 4] {
+        Direction::ALL.map(|dir| self.step(point, dir))
+    }
+
+    pub fn neighbors8(&self, point: IPoint) -> [IPoint; 8] {
+        Direction8::ALL.map(|dir| self.step8(point, dir))
+    }
+
+    /// The occupied cells among the four neighbours of `point`.
+    pub fn occupied_neighbors(&self, point: IPoint) -> impl Iterator<Item = (IPoint, &T)> {
+        self.neighbors(point)
+            .into_iter()
+            .filter_map(|n| self.get(n).map(|value| (n, value)))
+    }
+
+    /// Copies the occupied cells of `map` for which `keep` holds.
+    pub fn from_pointmap(map: &PointMap<T>, keep: impl Fn(&T) -> bool) -> Self
+    where
+        T: Clone,
+    {
+        let mut sparse = Self::new();
+        for point in map.points() {
+            if keep(map.at(point)) {
+                sparse.insert(IPoint::from_point(point), map.at(point).clone());
+            }
+        }
+        sparse
+    }
+
+    /// Converts into a dense map covering the bounding box, filling unoccupied
+    /// cells with `empty`. Also returns the position of the dense map's origin.
+    pub fn to_pointmap(&self, empty: T) -> (PointMap<T>, IPoint)
+    where
+        T: Clone,
+    {
+        let min = self.bounds.min;
+        let (width, height) = (self.width(), self.height());
+        let mut map = PointMap::new(vec![empty; width * height], width, height);
+        for (point, value) in self.iter() {
+            map.set(Point::from_ipoint(point - min), value.clone());
+        }
+        (map, min)
+    }
+
+    /// Draws the bounding box as lines of text, one character per cell.
+    pub fn render(&self, cell: impl Fn(Option<&T>) -> char) -> String {
+        let mut out = String::new();
+        let Rect { min, max } = self.bounds;
+        for y in min.y..max.y {
+            out.extend((min.x..max.x).map(|x| cell(self.get(IPoint::new(x, y)))));
+            out.push('\n');
+        }
+        out
+    }
+}
+
+impl<T> From<PointMap<T>> for SparseMap<T> {
+    fn from(map: PointMap<T>) -> Self {
+        let mut sparse = Self::new();
+        for (i, value) in map.vec.into_iter().enumerate() {
+            let point = IPoint::new((i % map.width) as isize, (i / map.width) as isize);
+            sparse.insert(point, value);
+        }
+        sparse
+    }
+}
+
+impl<T> FromIterator<(IPoint, T)> for SparseMap<T> {
+    fn from_iter<I: IntoIterator<Item = (IPoint, T)>>(iter: I) -> Self {
+        let mut sparse = Self::new();
+        for (point, value) in iter {
+            sparse.insert(point, value);
+        }
+        sparse
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sparse(points: &[(isize, isize)]) -> SparseMap<char> {
+        points.iter().map(|&(x, y)| (IPoint::new(x, y), '#')).collect()
+    }
+
+    #[test]
+    fn test_bounds() {
+        let mut map = sparse(&[(-2, 1), (0, 0), (3, -1), (1, 2)]);
+        assert_eq!(map.bounds(), Rect::new(IPoint::new(-2, -1), IPoint::new(4, 3)));
+        assert_eq!((map.width(), map.height()), (6, 4));
+
+        // Removing an inner cell leaves the bounds alone
+        map.remove(IPoint::new(0, 0));
+        assert_eq!(map.bounds(), Rect::new(IPoint::new(-2, -1), IPoint::new(4, 3)));
+
+        map.remove(IPoint::new(3, -1));
+        assert_eq!(map.bounds(), Rect::new(IPoint::new(-2, 1), IPoint::new(2, 3)));
+        map.remove(IPoint::new(-2, 1));
+        assert_eq!(map.bounds(), Rect::new(IPoint::new(1, 2), IPoint::new(2, 3)));
+
+        assert_eq!(map.remove(IPoint::new(1, 2)), Some('#'));
+        assert_eq!(map.remove(IPoint::new(1, 2)), None);
+        assert!(map.bounds().is_empty());
+        assert_eq!((map.width(), map.height()), (0, 0));
+    }
+
+    #[test]
+    fn test_to_pointmap() {
+        let map = sparse(&[(-1, -2), (1, -1)]);
+        let (dense, origin) = map.to_pointmap('.');
+        assert_eq!(origin, IPoint::new(-1, -2));
+        assert_eq!((dense.width, dense.height), (3, 2));
+        assert_eq!(dense.vec, ['#', '.', '.', '.', '.', '#']);
+
+        let (empty, origin) = SparseMap::new().to_pointmap('.');
+        assert_eq!(origin, IPoint::new(0, 0));
+        assert!(empty.vec.is_empty());
+    }
+
+    #[test]
+    fn test_render() {
+        let map = sparse(&[(5, 5), (7, 6)]);
+        assert_eq!(map.render(|c| c.copied().unwrap_or('.')), "#..\n..#\n");
+        assert_eq!(SparseMap::<char>::new().render(|_| '#'), "");
+    }
+}